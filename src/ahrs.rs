@@ -0,0 +1,223 @@
+//! Attitude and heading reference system filters.
+//!
+//! The attitude rotates body-frame vectors into the earth frame, whose z axis points
+//! up. Gyroscope samples are body rates in radians per second; accelerometer and
+//! magnetometer samples may be in any unit, only their directions are used.
+
+use num_traits::Float;
+use crate::vector::{add, cross, norm, scale};
+use crate::{cast, Quaternion, UnitQuaternion};
+
+fn normalized<T: Float>(alpha: [T; 3]) -> Option<[T; 3]> {
+    let n = norm(alpha);
+    if n.is_normal() { Some(scale(alpha, T::one() / n)) } else { None }
+}
+
+/// Earth-frame magnetic reference `[bx, 0, bz]` with the same inclination as `mag`
+/// measured at `attitude`.
+fn magnetic_reference<T: Float>(attitude: Quaternion<T>, mag: [T; 3]) -> [T; 3] {
+    let h = UnitQuaternion::new_unchecked(attitude).rotate_vector(mag);
+    [(h[0] * h[0] + h[1] * h[1]).sqrt(), T::zero(), h[2]]
+}
+
+/// Gradient of `|q^-1 * d * q - s|^2 / 2` with respect to the components of `q`.
+fn gradient<T: Float>(q: Quaternion<T>, d: [T; 3], s: [T; 3]) -> Quaternion<T> {
+    let estimate = UnitQuaternion::new_unchecked(q).inverse_rotate_vector(d);
+    let f = Quaternion::from_vector([estimate[0] - s[0], estimate[1] - s[1], estimate[2] - s[2]]);
+    Quaternion::from_vector(d) * q * f * cast::<T>(-2.0)
+}
+
+/// Madgwick's gradient descent orientation filter.
+///
+/// Each update takes a normalized gradient step of length `beta * sample_period`, so
+/// a large `beta` converges quickly but leaves a residual jitter of that size.
+#[derive(Copy, Clone, Debug)]
+pub struct Madgwick<T = f64> {
+    /// Gain of the gradient descent step, in radians per second.
+    pub beta: T,
+    /// Time between samples, in seconds.
+    pub sample_period: T,
+    attitude: Quaternion<T>,
+}
+
+impl<T: Float> Madgwick<T> {
+    pub fn new(sample_period: T, beta: T) -> Madgwick<T> {
+        Self { beta, sample_period, attitude: Quaternion::identity() }
+    }
+    pub fn attitude(&self) -> Quaternion<T> {
+        self.attitude
+    }
+    pub fn set_attitude(&mut self, attitude: Quaternion<T>) {
+        self.attitude = attitude.unit();
+    }
+    /// Integrates one gyroscope sample, corrected towards the accelerometer and, if
+    /// given, magnetometer directions. Zero vector samples are ignored.
+    pub fn update(&mut self, gyro: [T; 3], accel: [T; 3], mag: Option<[T; 3]>) {
+        let q = self.attitude;
+        let mut q_dot = q * Quaternion::from_vector(gyro) * cast::<T>(0.5);
+        if let Some(a) = normalized(accel) {
+            let up = [T::zero(), T::zero(), T::one()];
+            let mut step = gradient(q, up, a);
+            if let Some(m) = mag.and_then(normalized) {
+                step += gradient(q, magnetic_reference(q, m), m);
+            }
+            if let Ok(step) = step.try_unit() {
+                q_dot -= step * self.beta;
+            }
+        }
+        self.attitude = (q + q_dot * self.sample_period).unit();
+    }
+}
+
+/// Mahony's nonlinear complementary filter with proportional-integral feedback.
+#[derive(Copy, Clone, Debug)]
+pub struct Mahony<T = f64> {
+    /// Proportional gain, in radians per second.
+    pub kp: T,
+    /// Integral gain, in radians per second squared; zero disables bias estimation.
+    pub ki: T,
+    /// Time between samples, in seconds.
+    pub sample_period: T,
+    attitude: Quaternion<T>,
+    integral: [T; 3],
+}
+
+impl<T: Float> Mahony<T> {
+    pub fn new(sample_period: T, kp: T, ki: T) -> Mahony<T> {
+        Self { kp, ki, sample_period, attitude: Quaternion::identity(), integral: [T::zero(); 3] }
+    }
+    pub fn attitude(&self) -> Quaternion<T> {
+        self.attitude
+    }
+    pub fn set_attitude(&mut self, attitude: Quaternion<T>) {
+        self.attitude = attitude.unit();
+    }
+    /// Integral feedback term, an estimate of the negated gyroscope bias.
+    pub fn integral(&self) -> [T; 3] {
+        self.integral
+    }
+    /// Integrates one gyroscope sample, corrected towards the accelerometer and, if
+    /// given, magnetometer directions. Zero vector samples are ignored.
+    pub fn update(&mut self, gyro: [T; 3], accel: [T; 3], mag: Option<[T; 3]>) {
+        let q = self.attitude;
+        let rotation = UnitQuaternion::new_unchecked(q);
+        let mut omega = gyro;
+        if let Some(a) = normalized(accel) {
+            let up = rotation.inverse_rotate_vector([T::zero(), T::zero(), T::one()]);
+            let mut error = cross(a, up);
+            if let Some(m) = mag.and_then(normalized) {
+                let north = rotation.inverse_rotate_vector(magnetic_reference(q, m));
+                error = add(error, cross(m, north));
+            }
+            if self.ki > T::zero() {
+                self.integral = add(self.integral, scale(error, self.ki * self.sample_period));
+                omega = add(omega, self.integral);
+            }
+            omega = add(omega, scale(error, self.kp));
+        }
+        let q_dot = q * Quaternion::from_vector(omega) * cast::<T>(0.5);
+        self.attitude = (q + q_dot * self.sample_period).unit();
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Madgwick, Mahony};
+    use crate::{lie, Quaternion, UnitQuaternion};
+
+    const DT: f64 = 0.01;
+    const MAG: [f64; 3] = [0.4, 0.0, -0.9];
+
+    /// Gyro, accelerometer and magnetometer samples of a body at `attitude`.
+    fn sensors(attitude: Quaternion, rate: [f64; 3]) -> ([f64; 3], [f64; 3], [f64; 3]) {
+        let r = UnitQuaternion::new(attitude);
+        (rate, r.inverse_rotate_vector([0.0, 0.0, 9.81]), r.inverse_rotate_vector(MAG))
+    }
+
+    fn tilt(q: Quaternion) -> [f64; 3] {
+        UnitQuaternion::new(q).inverse_rotate_vector([0.0, 0.0, 1.0])
+    }
+
+    fn angle(a: Quaternion, b: Quaternion) -> f64 {
+        (a.conj() * b).to_axis_angle().1
+    }
+
+    trait Filter {
+        fn step(&mut self, gyro: [f64; 3], accel: [f64; 3], mag: Option<[f64; 3]>);
+        fn estimate(&self) -> Quaternion;
+    }
+
+    impl Filter for Madgwick {
+        fn step(&mut self, gyro: [f64; 3], accel: [f64; 3], mag: Option<[f64; 3]>) {
+            self.update(gyro, accel, mag)
+        }
+        fn estimate(&self) -> Quaternion {
+            self.attitude()
+        }
+    }
+
+    impl Filter for Mahony {
+        fn step(&mut self, gyro: [f64; 3], accel: [f64; 3], mag: Option<[f64; 3]>) {
+            self.update(gyro, accel, mag)
+        }
+        fn estimate(&self) -> Quaternion {
+            self.attitude()
+        }
+    }
+
+    fn filters() -> [Box<dyn Filter>; 2] {
+        [Box::new(Madgwick::new(DT, 0.05)), Box::new(Mahony::new(DT, 2.0, 0.0))]
+    }
+
+    #[test]
+    fn test_static_convergence() {
+        let truth = Quaternion::from_euler(crate::EulerSequence::Intrinsic(crate::EulerAxes::ZYX), 1.0, 0.4, -0.3);
+        for mut filter in filters() {
+            let (gyro, accel, mag) = sensors(truth, [0.0; 3]);
+            for _ in 0..4000 {
+                filter.step(gyro, accel, Some(mag));
+            }
+            assert!(angle(filter.estimate(), truth) < 2e-3, "{}", filter.estimate());
+        }
+    }
+    #[test]
+    fn test_tilt_without_magnetometer() {
+        let truth = Quaternion::from_axis_angle([1.0, -1.0, 0.0], 0.6);
+        for mut filter in filters() {
+            let (gyro, accel, _) = sensors(truth, [0.0; 3]);
+            for _ in 0..4000 {
+                filter.step(gyro, accel, None);
+            }
+            let (a, b) = (tilt(filter.estimate()), tilt(truth));
+            assert!((0..3).all(|n| (a[n] - b[n]).abs() < 2e-3), "{:?} != {:?}", a, b);
+        }
+    }
+    #[test]
+    fn test_rotating_trajectory() {
+        let rate = [0.2, -0.3, 0.5];
+        let start = Quaternion::from_axis_angle([0.0, 1.0, 0.0], 0.3);
+        for mut filter in filters() {
+            let mut truth = start;
+            for _ in 0..2000 {
+                truth *= lie::exp([rate[0] * DT, rate[1] * DT, rate[2] * DT]).quaternion();
+                let (gyro, accel, mag) = sensors(truth, rate);
+                filter.step(gyro, accel, Some(mag));
+            }
+            assert!(angle(filter.estimate(), truth) < 0.02, "{} != {}", filter.estimate(), truth);
+        }
+    }
+    #[test]
+    fn test_mahony_gyro_bias() {
+        let truth = Quaternion::from_axis_angle([1.0, 2.0, 0.5], 0.8);
+        let bias = [0.02, -0.01, 0.03];
+        let mut filter = Mahony::new(DT, 1.0, 0.3);
+        filter.set_attitude(truth);
+        let (_, accel, mag) = sensors(truth, [0.0; 3]);
+        for _ in 0..6000 {
+            filter.update(bias, accel, Some(mag));
+        }
+        let integral = filter.integral();
+        assert!((0..3).all(|n| (integral[n] + bias[n]).abs() < 1e-3), "{:?}", integral);
+        assert!(angle(filter.attitude(), truth) < 1e-3);
+    }
+}
@@ -0,0 +1,152 @@
+//! Weighted means of rotations. All means treat `q` and `-q` as the same rotation
+//! and return a unit quaternion with non-negative real part.
+//!
+//! # Panics
+//!
+//! The functions panic if `quaternions` and `weights` differ in length.
+
+use num_traits::Float;
+use crate::vector::{norm, scale, symmetric_eigen};
+use crate::{lie, Quaternion, QuaternionError, UnitQuaternion};
+
+fn total_weight<T: Float>(quaternions: &[Quaternion<T>], weights: &[T]) -> Result<T, QuaternionError> {
+    assert_eq!(quaternions.len(), weights.len(), "one weight per quaternion is required");
+    let total = weights.iter().fold(T::zero(), |sum, w| sum + *w);
+    if total > T::zero() { Ok(total) } else { Err(QuaternionError::Degenerate) }
+}
+
+/// Markley's average: the eigenvector with the largest eigenvalue of
+/// `sum(weight * q q^T)`. It minimizes the weighted squared Frobenius distance
+/// between rotation matrices.
+pub fn markley_mean<T: Float>(quaternions: &[Quaternion<T>], weights: &[T]) -> Result<Quaternion<T>, QuaternionError> {
+    total_weight(quaternions, weights)?;
+    let mut m = [[T::zero(); 4]; 4];
+    for (q, w) in quaternions.iter().zip(weights) {
+        let q = q.try_unit()?;
+        let c = [q.real(), q.vector()[0], q.vector()[1], q.vector()[2]];
+        for row in 0..4 {
+            for col in 0..4 {
+                m[row][col] = m[row][col] + *w * c[row] * c[col];
+            }
+        }
+    }
+    let (values, vectors) = symmetric_eigen(m);
+    let best = (1..4).fold(0, |b, n| if values[n] > values[b] { n } else { b });
+    let q = Quaternion::new(vectors[0][best], vectors[1][best], vectors[2][best], vectors[3][best]);
+    Ok(q.try_unit()?.positive_hemisphere())
+}
+
+/// Upper bound on the sign realignments in `chordal_mean`. Each realignment strictly
+/// lowers the cost and there are finitely many sign patterns, so this is only reached
+/// when ties (a `q_i` orthogonal to the mean) make the alignment cycle.
+const MAX_REALIGNMENTS: usize = 64;
+
+/// Quaternion chordal L2 mean: the unit quaternion minimizing
+/// `sum(weight * min(|q - q_i|^2, |q + q_i|^2))`, the normalized weighted sum after
+/// aligning every `q_i` with the result.
+///
+/// Starting from `markley_mean`, the signs of the `q_i` are realigned with the current
+/// mean until they stop changing; the mean is then an exact fixed point. Fails with
+/// `Degenerate` if the alignment has not settled after `MAX_REALIGNMENTS` rounds.
+pub fn chordal_mean<T: Float>(quaternions: &[Quaternion<T>], weights: &[T]) -> Result<Quaternion<T>, QuaternionError> {
+    let mut mean = markley_mean(quaternions, weights)?;
+    let mut signs: Vec<bool> = Vec::new();
+    for _ in 0..MAX_REALIGNMENTS {
+        let next: Vec<bool> = quaternions.iter().map(|q| q.dot(&mean) < T::zero()).collect();
+        if next == signs {
+            return Ok(mean.positive_hemisphere());
+        }
+        let sum = quaternions.iter().zip(weights).zip(&next).fold(Quaternion::zero(), |sum, ((q, w), negative)| {
+            let q = q.unit();
+            sum + if *negative { -q } else { q } * *w
+        });
+        mean = sum.try_unit().map_err(|_| QuaternionError::Degenerate)?;
+        signs = next;
+    }
+    Err(QuaternionError::Degenerate)
+}
+
+/// Karcher mean: the rotation minimizing the weighted sum of squared geodesic angles,
+/// found by Gauss-Newton iteration on the tangent space from `markley_mean`.
+pub fn karcher_mean<T: Float>(quaternions: &[Quaternion<T>], weights: &[T]) -> Result<Quaternion<T>, QuaternionError> {
+    let total = total_weight(quaternions, weights)?;
+    let mut mean = UnitQuaternion::new_unchecked(markley_mean(quaternions, weights)?);
+    for _ in 0..100 {
+        let step = quaternions.iter().zip(weights).fold([T::zero(); 3], |sum, (q, w)| {
+            let delta = lie::boxminus(UnitQuaternion::new(*q), mean);
+            [sum[0] + *w * delta[0], sum[1] + *w * delta[1], sum[2] + *w * delta[2]]
+        });
+        let step = scale(step, T::one() / total);
+        mean = lie::boxplus(mean, step);
+        if norm(step) <= T::epsilon() * (T::one() + T::one()) {
+            break;
+        }
+    }
+    Ok(mean.quaternion().positive_hemisphere())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn about_z(angle: f64) -> Quaternion {
+        Quaternion::from_axis_angle([0.0, 0.0, 1.0], angle)
+    }
+
+    #[test]
+    fn test_symmetric_set() {
+        let center = Quaternion::from_axis_angle([1.0, 2.0, -1.0], 2.5);
+        let offsets = [[0.3, 0.0, 0.0], [-0.3, 0.0, 0.0], [0.0, 0.2, 0.1], [0.0, -0.2, -0.1]];
+        let quaternions: Vec<Quaternion> = offsets
+            .iter()
+            .enumerate()
+            .map(|(n, v)| {
+                let q = (UnitQuaternion::new(center) * lie::exp(*v)).quaternion();
+                if n % 2 == 0 { -q } else { q }
+            })
+            .collect();
+        let weights = [1.0; 4];
+        for mean in [markley_mean, chordal_mean, karcher_mean] {
+            let q = mean(&quaternions, &weights).unwrap();
+            assert!(q.rotation_eq(&center, 1e-12), "{} != {}", q, center);
+            assert!(q.real() >= 0.0);
+        }
+    }
+    #[test]
+    fn test_weighted() {
+        let quaternions = [about_z(0.0), -about_z(1.0)];
+        let weights = [1.0, 3.0];
+        let q = karcher_mean(&quaternions, &weights).unwrap();
+        assert!(q.rotation_eq(&about_z(0.75), 1e-12), "{}", q);
+        let m = markley_mean(&quaternions, &weights).unwrap();
+        let c = chordal_mean(&quaternions, &weights).unwrap();
+        assert!(m.rotation_eq(&about_z(0.75), 0.05) && c.rotation_eq(&about_z(0.75), 0.05));
+        let angle = |q: Quaternion| q.to_axis_angle().1;
+        assert!(angle(m) > 0.5 && angle(m) < 1.0);
+        assert!(angle(c) > 0.5 && angle(c) < 1.0);
+    }
+    #[test]
+    fn test_chordal_fixed_point() {
+        let quaternions: Vec<Quaternion> = (0..7)
+            .map(|n| {
+                let t = n as f64;
+                let q = Quaternion::from_axis_angle([t.sin(), t.cos(), 0.3 * t], 0.5 * t);
+                if n % 3 == 0 { -q } else { q }
+            })
+            .collect();
+        let weights = [1.0, 0.5, 2.0, 1.5, 0.7, 1.0, 3.0];
+        let c = chordal_mean(&quaternions, &weights).unwrap();
+        let sum = quaternions.iter().zip(&weights).fold(Quaternion::zero(), |sum, (q, w)| {
+            sum + if q.dot(&c) < 0.0 { -*q } else { *q } * *w
+        });
+        assert!(sum.unit().approx_eq(&c, 1e-15), "{} != {}", sum.unit(), c);
+        let flipped: Vec<Quaternion> = quaternions.iter().map(|q| -q).collect();
+        assert!(chordal_mean(&flipped, &weights).unwrap().approx_eq(&c, 1e-15));
+    }
+    #[test]
+    fn test_degenerate() {
+        assert_eq!(markley_mean::<f64>(&[], &[]), Err(QuaternionError::Degenerate));
+        assert_eq!(karcher_mean(&[about_z(1.0)], &[0.0]), Err(QuaternionError::Degenerate));
+        assert!(karcher_mean(&[about_z(1.0)], &[2.0]).unwrap().rotation_eq(&about_z(1.0), 1e-15));
+    }
+}
@@ -0,0 +1,121 @@
+use num_traits::Float;
+use crate::{cast, Quaternion};
+
+fn components<T: Copy>(alpha: &Quaternion<T>) -> [T; 4] {
+    [alpha.i, alpha.j, alpha.k, alpha.l]
+}
+
+/// Distance between neighbouring floats at the magnitude of `alpha`.
+fn ulp<T: Float>(alpha: T) -> T {
+    let (_, exponent, _) = alpha.integer_decode();
+    cast::<T>(2.0).powi(exponent as i32)
+}
+
+impl<T: Float> Quaternion<T> {
+    /// Whether every component differs from `alpha` by at most `eps`.
+    pub fn approx_eq(&self, alpha: &Quaternion<T>, eps: T) -> bool {
+        components(self).iter().zip(components(alpha)).all(|(a, b)| (*a - b).abs() <= eps)
+    }
+    /// Whether every component is within `eps` absolutely or within `max_relative`
+    /// times the larger magnitude of the two.
+    pub fn relative_eq(&self, alpha: &Quaternion<T>, eps: T, max_relative: T) -> bool {
+        components(self).iter().zip(components(alpha)).all(|(a, b)| {
+            let d = (*a - b).abs();
+            d <= eps || d <= max_relative * a.abs().max(b.abs())
+        })
+    }
+    /// Whether every component is within `eps` absolutely or at most `max_ulps`
+    /// representable floats away, counted at the larger magnitude of the two.
+    pub fn ulps_eq(&self, alpha: &Quaternion<T>, eps: T, max_ulps: u32) -> bool {
+        components(self).iter().zip(components(alpha)).all(|(a, b)| {
+            let d = (*a - b).abs();
+            if d <= eps {
+                return true;
+            }
+            a.is_sign_negative() == b.is_sign_negative() && d <= ulp(a.abs().max(b.abs())) * cast(max_ulps as f64)
+        })
+    }
+    /// Whether `self` and `alpha` represent the same rotation within `eps`, treating
+    /// `q` and `-q` as equal.
+    pub fn rotation_eq(&self, alpha: &Quaternion<T>, eps: T) -> bool {
+        self.approx_eq(alpha, eps) || self.approx_eq(&-*alpha, eps)
+    }
+}
+
+#[cfg(feature = "approx")]
+impl<T: Float + approx::AbsDiffEq<Epsilon = T>> approx::AbsDiffEq for Quaternion<T> {
+    type Epsilon = T;
+    fn default_epsilon() -> T {
+        T::default_epsilon()
+    }
+    fn abs_diff_eq(&self, other: &Quaternion<T>, epsilon: T) -> bool {
+        components(self).iter().zip(components(other)).all(|(a, b)| a.abs_diff_eq(&b, epsilon))
+    }
+}
+
+#[cfg(feature = "approx")]
+impl<T: Float + approx::RelativeEq<Epsilon = T>> approx::RelativeEq for Quaternion<T> {
+    fn default_max_relative() -> T {
+        T::default_max_relative()
+    }
+    fn relative_eq(&self, other: &Quaternion<T>, epsilon: T, max_relative: T) -> bool {
+        components(self).iter().zip(components(other)).all(|(a, b)| a.relative_eq(&b, epsilon, max_relative))
+    }
+}
+
+#[cfg(feature = "approx")]
+impl<T: Float + approx::UlpsEq<Epsilon = T>> approx::UlpsEq for Quaternion<T> {
+    fn default_max_ulps() -> u32 {
+        T::default_max_ulps()
+    }
+    fn ulps_eq(&self, other: &Quaternion<T>, epsilon: T, max_ulps: u32) -> bool {
+        components(self).iter().zip(components(other)).all(|(a, b)| a.ulps_eq(&b, epsilon, max_ulps))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::Quaternion;
+
+    #[test]
+    fn test_approx_eq() {
+        let q = Quaternion::new(14.0, -19.0, 9.0, -3.0);
+        let r = q.unit() * q.abs();
+        assert!(q.approx_eq(&r, 1e-12));
+        assert!(!q.approx_eq(&(q + Quaternion::new(0.0, 0.0, 1e-6, 0.0)), 1e-12));
+        assert!(q.relative_eq(&(q * (1.0 + 1e-10)), 0.0, 1e-9));
+        assert!(!q.relative_eq(&(q * (1.0 + 1e-8)), 0.0, 1e-9));
+    }
+    #[test]
+    fn test_ulps_eq() {
+        let a = Quaternion::new(1.0, -2.0, 1e10, 0.0);
+        let b = Quaternion::new(1.0 + f64::EPSILON, -2.0, 1e10 + 1e10 * f64::EPSILON, 0.0);
+        assert!(a.ulps_eq(&b, 0.0, 2));
+        assert!(!a.ulps_eq(&(a + Quaternion::new(1e-12, 0.0, 0.0, 0.0)), 0.0, 4));
+        assert!(!Quaternion::new(1e-300, 0.0, 0.0, 0.0).ulps_eq(&Quaternion::new(-1e-300, 0.0, 0.0, 0.0), 0.0, 4));
+        assert!(Quaternion::new(1e-300, 0.0, 0.0, 0.0).ulps_eq(&Quaternion::new(-1e-300, 0.0, 0.0, 0.0), 1e-299, 4));
+    }
+    #[test]
+    fn test_rotation_eq() {
+        let q = Quaternion::from_axis_angle([1.0, 2.0, 3.0], 0.7);
+        assert!(q.rotation_eq(&-q, 1e-15));
+        assert!(!q.approx_eq(&-q, 1e-15));
+        assert!(!q.rotation_eq(&q.conj(), 1e-3));
+    }
+    #[test]
+    fn test_exchangeable() {
+        let q = Quaternion::new(0.1, 0.2, 0.3, 0.4);
+        assert!(q.exchangeable(&(q * 3.0 + Quaternion::new(1.0, 0.0, 0.0, 0.0)), 1e-15));
+        assert!(q.exchangeable(&q.exp().ln(), 1e-15));
+        assert!(!q.exchangeable(&Quaternion::new(0.0, 1.0, 0.0, 0.0), 1e-3));
+    }
+    #[cfg(feature = "approx")]
+    #[test]
+    fn test_approx_traits() {
+        let q = Quaternion::new(1.0, 2.0, 3.0, 4.0);
+        approx::assert_abs_diff_eq!(q, q + Quaternion::new(1e-10, 0.0, 0.0, 0.0), epsilon = 1e-9);
+        approx::assert_relative_eq!(q, q * (1.0 + 1e-12), max_relative = 1e-10);
+        approx::assert_ulps_eq!(q, q);
+        approx::assert_abs_diff_ne!(q, -q);
+    }
+}
@@ -0,0 +1,267 @@
+use num_traits::Float;
+use crate::vector::{cross, dot, norm, scale};
+use crate::{cast, Quaternion};
+
+fn small_angle<T: Float>() -> T {
+    T::epsilon().sqrt().sqrt()
+}
+
+impl<T: Float> Quaternion<T> {
+    /// Rotation by `angle` radians about `axis`, which need not be normalized.
+    /// A zero axis yields the identity.
+    pub fn from_axis_angle(axis: [T; 3], angle: T) -> Quaternion<T> {
+        let n = norm(axis);
+        if n == T::zero() {
+            return Quaternion::identity();
+        }
+        let half = angle / cast(2.0);
+        let s = half.sin() / n;
+        Quaternion::new(half.cos(), axis[0] * s, axis[1] * s, axis[2] * s)
+    }
+    /// Rotation about `alpha / |alpha|` by `|alpha|` radians.
+    pub fn from_rotation_vector(alpha: [T; 3]) -> Quaternion<T> {
+        let theta = norm(alpha);
+        let half = theta / cast(2.0);
+        // sin(theta / 2) / theta, with its Taylor expansion near zero
+        let s = if theta < small_angle() {
+            cast::<T>(0.5) - theta * theta / cast(48.0)
+        } else {
+            half.sin() / theta
+        };
+        Quaternion::new(half.cos(), alpha[0] * s, alpha[1] * s, alpha[2] * s)
+    }
+    /// Unit axis and angle in `[0, pi]` of the rotation represented by `self`.
+    /// For the identity the axis is `[1, 0, 0]`.
+    pub fn to_axis_angle(&self) -> ([T; 3], T) {
+        let q = self.positive_hemisphere();
+        let n = norm(q.vector());
+        if n == T::zero() {
+            return ([T::one(), T::zero(), T::zero()], T::zero());
+        }
+        let angle = cast::<T>(2.0) * n.atan2(q.i);
+        ([q.j / n, q.k / n, q.l / n], angle)
+    }
+    /// Rotation vector with norm in `[0, pi]`, the inverse of `from_rotation_vector`.
+    pub fn to_rotation_vector(&self) -> [T; 3] {
+        let q = self.positive_hemisphere();
+        let n = norm(q.vector());
+        let x = n / q.i;
+        // angle / n, with its Taylor expansion near zero
+        let s = if x.abs() < small_angle() {
+            cast::<T>(2.0) / q.i * (T::one() - x * x / cast(3.0))
+        } else {
+            cast::<T>(2.0) * n.atan2(q.i) / n
+        };
+        [q.j * s, q.k * s, q.l * s]
+    }
+    /// Row-major direction cosine matrix `m` with `m * v` equal to `self` rotating `v`.
+    /// `self` need not be normalized.
+    pub fn to_rotation_matrix(&self) -> [[T; 3]; 3] {
+        let s = cast::<T>(2.0) / (self.i * self.i + self.j * self.j + self.k * self.k + self.l * self.l);
+        let (w, x, y, z) = (self.i, self.j, self.k, self.l);
+        [
+            [T::one() - s * (y * y + z * z), s * (x * y - w * z), s * (x * z + w * y)],
+            [s * (x * y + w * z), T::one() - s * (x * x + z * z), s * (y * z - w * x)],
+            [s * (x * z - w * y), s * (y * z + w * x), T::one() - s * (x * x + y * y)],
+        ]
+    }
+    /// Unit quaternion with non-negative real part from a row-major rotation matrix.
+    ///
+    /// Uses Shepperd's method, which takes the square root of the largest of the
+    /// trace and the diagonal elements to avoid cancellation.
+    pub fn from_rotation_matrix(m: [[T; 3]; 3]) -> Quaternion<T> {
+        let one = T::one();
+        let quarter: T = cast(0.25);
+        let trace = m[0][0] + m[1][1] + m[2][2];
+        let q = if trace >= m[0][0] && trace >= m[1][1] && trace >= m[2][2] {
+            let w = (one + trace).sqrt() * cast(0.5);
+            let f = quarter / w;
+            Quaternion::new(w, (m[2][1] - m[1][2]) * f, (m[0][2] - m[2][0]) * f, (m[1][0] - m[0][1]) * f)
+        } else if m[0][0] >= m[1][1] && m[0][0] >= m[2][2] {
+            let x = (one + m[0][0] - m[1][1] - m[2][2]).sqrt() * cast(0.5);
+            let f = quarter / x;
+            Quaternion::new((m[2][1] - m[1][2]) * f, x, (m[0][1] + m[1][0]) * f, (m[0][2] + m[2][0]) * f)
+        } else if m[1][1] >= m[2][2] {
+            let y = (one - m[0][0] + m[1][1] - m[2][2]).sqrt() * cast(0.5);
+            let f = quarter / y;
+            Quaternion::new((m[0][2] - m[2][0]) * f, (m[0][1] + m[1][0]) * f, y, (m[1][2] + m[2][1]) * f)
+        } else {
+            let z = (one - m[0][0] - m[1][1] + m[2][2]).sqrt() * cast(0.5);
+            let f = quarter / z;
+            Quaternion::new((m[1][0] - m[0][1]) * f, (m[0][2] + m[2][0]) * f, (m[1][2] + m[2][1]) * f, z)
+        };
+        q.unit().positive_hemisphere()
+    }
+    /// Homogeneous 4x4 transform with the rotation of `self` and no translation.
+    pub fn to_homogeneous_matrix(&self) -> [[T; 4]; 4] {
+        let r = self.to_rotation_matrix();
+        let mut m = [[T::zero(); 4]; 4];
+        for row in 0..3 {
+            m[row][..3].copy_from_slice(&r[row]);
+        }
+        m[3][3] = T::one();
+        m
+    }
+    /// Rotation part of a homogeneous 4x4 transform; the translation is ignored.
+    pub fn from_homogeneous_matrix(m: [[T; 4]; 4]) -> Quaternion<T> {
+        let mut r = [[T::zero(); 3]; 3];
+        for row in 0..3 {
+            r[row].copy_from_slice(&m[row][..3]);
+        }
+        Quaternion::from_rotation_matrix(r)
+    }
+    /// Shortest rotation taking the direction of `alpha` to the direction of `beta`.
+    /// For antiparallel vectors the half-turn is about an axis perpendicular to
+    /// `alpha`; a zero vector yields the identity.
+    pub fn from_two_vectors(alpha: [T; 3], beta: [T; 3]) -> Quaternion<T> {
+        let nn = norm(alpha) * norm(beta);
+        if nn == T::zero() {
+            return Quaternion::identity();
+        }
+        let w = nn + dot(alpha, beta);
+        if w <= T::epsilon() * nn {
+            // cross with the coordinate axis least aligned with alpha
+            let a = alpha.map(|x| x.abs());
+            let other = if a[0] <= a[1] && a[0] <= a[2] {
+                [T::one(), T::zero(), T::zero()]
+            } else if a[1] <= a[2] {
+                [T::zero(), T::one(), T::zero()]
+            } else {
+                [T::zero(), T::zero(), T::one()]
+            };
+            return Quaternion::from_vector(cross(alpha, other)).unit();
+        }
+        let v = cross(alpha, beta);
+        Quaternion::new(w, v[0], v[1], v[2]).unit()
+    }
+    /// Rotation taking the local `+z` axis to `forward` and the local `+y` axis as
+    /// close to `up` as possible. Falls back to `from_two_vectors` when `up` is
+    /// parallel to `forward`.
+    pub fn look_rotation(forward: [T; 3], up: [T; 3]) -> Quaternion<T> {
+        let z = scale(forward, T::one() / norm(forward));
+        let x = cross(up, z);
+        let n = norm(x);
+        if n <= T::epsilon() * norm(up) {
+            return Quaternion::from_two_vectors([T::zero(), T::zero(), T::one()], forward);
+        }
+        let x = scale(x, T::one() / n);
+        Quaternion::from_basis(x, cross(z, x), z)
+    }
+    /// Rotation taking the local axes to `x`, `y` and `z`, which should form a
+    /// right-handed orthonormal basis.
+    pub fn from_basis(x: [T; 3], y: [T; 3], z: [T; 3]) -> Quaternion<T> {
+        Quaternion::from_rotation_matrix([[x[0], y[0], z[0]], [x[1], y[1], z[1]], [x[2], y[2], z[2]]])
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::f64::consts::PI;
+    use crate::vector::norm;
+    use crate::{Quaternion, UnitQuaternion};
+
+    fn assert_close(a: &[f64], b: &[f64], eps: f64) {
+        for n in 0..a.len() {
+            assert!((a[n] - b[n]).abs() < eps, "{:?} != {:?}", a, b);
+        }
+    }
+
+    #[test]
+    fn test_axis_angle() {
+        let q = Quaternion::from_axis_angle([0.0, 0.0, 2.0], PI / 2.0);
+        let h = 0.5f64.sqrt();
+        assert_close(&[q.i, q.j, q.k, q.l], &[h, 0.0, 0.0, h], 1e-15);
+        let (axis, angle) = q.to_axis_angle();
+        assert_close(&axis, &[0.0, 0.0, 1.0], 1e-15);
+        assert!((angle - PI / 2.0).abs() < 1e-15);
+        let (axis, angle) = (-q).to_axis_angle();
+        assert_close(&axis, &[0.0, 0.0, 1.0], 1e-15);
+        assert!((angle - PI / 2.0).abs() < 1e-15);
+    }
+    #[test]
+    fn test_rotation_vector() {
+        for v in [[0.3, -1.0, 2.0], [1e-9, 2e-9, -1e-9], [0.0, 0.0, 0.0], [0.0, PI - 1e-9, 0.0]] {
+            let q = Quaternion::from_rotation_vector(v);
+            assert!((q.abs() - 1.0).abs() < 1e-15);
+            assert_close(&q.to_rotation_vector(), &v, 1e-12);
+        }
+        let q = Quaternion::from_rotation_vector([0.0, 1e-10, 0.0]);
+        assert_close(&[q.i, q.j, q.k, q.l], &[1.0, 0.0, 5e-11, 0.0], 1e-20);
+        let r = Quaternion::from_axis_angle([1.0, 0.0, 0.0], PI).to_rotation_vector();
+        assert_close(&r, &[PI, 0.0, 0.0], 1e-12);
+    }
+    #[test]
+    fn test_rotation_matrix() {
+        let q = Quaternion::new(0.3, -0.4, 0.1, 0.85).unit();
+        let m = q.to_rotation_matrix();
+        let v = [0.5, -2.0, 1.5];
+        let mv: Vec<f64> = m.iter().map(|r| r[0] * v[0] + r[1] * v[1] + r[2] * v[2]).collect();
+        assert_close(&mv, &UnitQuaternion::new(q).rotate_vector(v), 1e-14);
+        assert_close(&(q * Quaternion::new(3.0, 0.0, 0.0, 0.0)).to_rotation_matrix()[1], &m[1], 1e-15);
+        let p = Quaternion::from_rotation_matrix(m);
+        assert_close(&[p.i, p.j, p.k, p.l], &[q.i, q.j, q.k, q.l], 1e-15);
+        let h = q.to_homogeneous_matrix();
+        assert_eq!(h[3], [0.0, 0.0, 0.0, 1.0]);
+        assert_eq!(h[0][3], 0.0);
+        let p = Quaternion::from_homogeneous_matrix(h);
+        assert_close(&[p.i, p.j, p.k, p.l], &[q.i, q.j, q.k, q.l], 1e-15);
+    }
+    #[test]
+    fn test_rotation_matrix_branches() {
+        for q in [
+            Quaternion::new(0.9, 0.1, -0.3, 0.2),
+            Quaternion::new(0.05, 0.9, -0.3, 0.2),
+            Quaternion::new(0.05, 0.2, -0.9, 0.3),
+            Quaternion::new(0.05, 0.2, -0.3, 0.9),
+            Quaternion::new(0.0, 0.0, 0.0, 1.0),
+        ] {
+            let q = q.unit();
+            let p = Quaternion::from_rotation_matrix(q.to_rotation_matrix());
+            assert!(p.i >= 0.0);
+            let d: f64 = p.i * q.i + p.j * q.j + p.k * q.k + p.l * q.l;
+            assert!((d.abs() - 1.0).abs() < 1e-14);
+        }
+    }
+    #[test]
+    fn test_from_two_vectors() {
+        let a: [f64; 3] = [1.0, 2.0, -0.5];
+        for b in [[-3.0, 0.5, 2.0], [2.0, 4.0, -1.0], [-1.0, -2.0, 0.5], [0.0, 0.0, 0.0]] {
+            let q = Quaternion::from_two_vectors(a, b);
+            assert!((q.abs() - 1.0).abs() < 1e-15);
+            if b == [0.0, 0.0, 0.0] {
+                assert_eq!(q, Quaternion::new(1.0, 0.0, 0.0, 0.0));
+                continue;
+            }
+            let r = UnitQuaternion::new(q).rotate_vector(a);
+            let s = norm(a) / norm(b);
+            assert_close(&r, &[b[0] * s, b[1] * s, b[2] * s], 1e-14);
+        }
+        let q = Quaternion::from_two_vectors([1.0, 0.0, 0.0], [0.0, 1.0, 0.0]);
+        let h = 0.5f64.sqrt();
+        assert_close(&[q.i, q.j, q.k, q.l], &[h, 0.0, 0.0, h], 1e-15);
+        for a in [[1.0f64, 0.0, 0.0], [0.0, -2.0, 0.0], [0.0, 0.0, 3.0]] {
+            let q = Quaternion::from_two_vectors(a, [-a[0], -a[1], -a[2]]);
+            assert!(q.i.abs() < 1e-15);
+            assert_close(&UnitQuaternion::new(q).rotate_vector(a), &[-a[0], -a[1], -a[2]], 1e-14);
+        }
+    }
+    #[test]
+    fn test_look_rotation() {
+        let q = UnitQuaternion::new(Quaternion::look_rotation([1.0, 0.0, 0.0], [0.0, 0.0, 1.0]));
+        assert_close(&q.rotate_vector([0.0, 0.0, 1.0]), &[1.0, 0.0, 0.0], 1e-15);
+        assert_close(&q.rotate_vector([0.0, 1.0, 0.0]), &[0.0, 0.0, 1.0], 1e-15);
+        let q = UnitQuaternion::new(Quaternion::look_rotation([0.0, 2.0, 2.0], [0.0, 1.0, 0.0]));
+        let h = 0.5f64.sqrt();
+        assert_close(&q.rotate_vector([0.0, 0.0, 1.0]), &[0.0, h, h], 1e-15);
+        assert_close(&q.rotate_vector([0.0, 1.0, 0.0]), &[0.0, h, -h], 1e-15);
+        let q = UnitQuaternion::new(Quaternion::look_rotation([0.0, 3.0, 0.0], [0.0, 1.0, 0.0]));
+        assert_close(&q.rotate_vector([0.0, 0.0, 1.0]), &[0.0, 1.0, 0.0], 1e-15);
+    }
+    #[test]
+    fn test_from_basis() {
+        let q = Quaternion::new(0.3, -0.4, 0.1, 0.85).unit();
+        let u = UnitQuaternion::new(q);
+        let p = Quaternion::from_basis(u.rotate_vector([1.0, 0.0, 0.0]), u.rotate_vector([0.0, 1.0, 0.0]), u.rotate_vector([0.0, 0.0, 1.0]));
+        assert_close(&[p.i, p.j, p.k, p.l], &[q.i, q.j, q.k, q.l], 1e-15);
+    }
+}
@@ -0,0 +1,97 @@
+//! Distances between rotations given as unit quaternions. `theta` below is the
+//! angle in `[0, pi]` of the relative rotation `alpha.conj() * beta`.
+
+use num_traits::Float;
+use crate::{cast, Quaternion};
+
+/// Geodesic distance `theta` in radians, the angle of the shortest rotation taking
+/// `alpha` to `beta`. A bi-invariant metric on rotations with range `[0, pi]`; it
+/// treats `q` and `-q` as equal and does not require normalized inputs.
+pub fn geodesic<T: Float>(alpha: Quaternion<T>, beta: Quaternion<T>) -> T {
+    let delta = alpha.conj() * beta;
+    cast::<T>(2.0) * delta.vector_abs().atan2(delta.real().abs())
+}
+
+/// Chordal distance `min(|alpha - beta|, |alpha + beta|) = 2 sin(theta / 4)`, the
+/// distance minimized by `average::chordal_mean`. A bi-invariant metric on rotations
+/// with range `[0, sqrt(2)]`, monotonic in `theta`.
+pub fn chordal<T: Float>(alpha: Quaternion<T>, beta: Quaternion<T>) -> T {
+    (alpha - beta).abs().min((alpha + beta).abs())
+}
+
+/// Inner product distance `1 - |<alpha, beta>| = 1 - cos(theta / 2)` with range
+/// `[0, 1]`. Cheap and monotonic in `theta`, but not a metric: it violates the
+/// triangle inequality.
+pub fn inner_product<T: Float>(alpha: Quaternion<T>, beta: Quaternion<T>) -> T {
+    (T::one() - alpha.dot(&beta).abs()).max(T::zero())
+}
+
+/// Deviation from identity `|alpha.conj() * beta - 1|`, equal to `|beta - alpha|`
+/// for unit `alpha`. A metric on unit quaternions with range `[0, 2]`, but not on
+/// rotations: it is `2 sin(theta / 4)` when `<alpha, beta> >= 0` and
+/// `2 cos(theta / 4)` otherwise, so `q` and `-q` are at distance 2.
+pub fn identity_deviation<T: Float>(alpha: Quaternion<T>, beta: Quaternion<T>) -> T {
+    (alpha.conj() * beta - Quaternion::identity()).abs()
+}
+
+#[cfg(test)]
+mod test {
+    use std::f64::consts::PI;
+    use super::*;
+
+    type Distance = fn(Quaternion, Quaternion) -> f64;
+
+    fn samples() -> Vec<Quaternion> {
+        (0..12)
+            .map(|n| {
+                let t = n as f64;
+                Quaternion::from_axis_angle([t.sin(), (1.7 * t).cos(), 0.5], 0.4 + 0.23 * t)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_known_angles() {
+        let a = Quaternion::from_axis_angle([1.0, -2.0, 0.5], 0.3);
+        for theta in [0.0, 0.2, PI / 2.0, 3.0, PI] {
+            let b = a * Quaternion::from_axis_angle([0.3, 0.1, -1.0], theta);
+            assert!((geodesic(a, b) - theta).abs() < 1e-14);
+            assert!((geodesic(a, -b) - theta).abs() < 1e-14);
+            assert!((geodesic(a * 3.0, b) - theta).abs() < 1e-14);
+            assert!((chordal(a, b) - 2.0 * (theta / 4.0).sin()).abs() < 1e-14);
+            assert!((chordal(a, -b) - chordal(a, b)).abs() < 1e-14);
+            assert!((inner_product(a, b) - (1.0 - (theta / 2.0).cos())).abs() < 1e-14);
+            assert!((inner_product(a, -b) - inner_product(a, b)).abs() < 1e-14);
+            assert!((identity_deviation(a, b) - 2.0 * (theta / 4.0).sin()).abs() < 1e-14);
+            assert!((identity_deviation(a, -b) - 2.0 * (theta / 4.0).cos()).abs() < 1e-14);
+        }
+        let b = a * Quaternion::from_axis_angle([0.3, 0.1, -1.0], 1e-9);
+        assert!((geodesic(a, b) - 1e-9).abs() < 1e-15);
+    }
+    #[test]
+    fn test_metric_properties() {
+        let q = samples();
+        let metrics: [Distance; 3] = [geodesic, chordal, identity_deviation];
+        for d in metrics.iter().chain([inner_product as Distance].iter()) {
+            for a in &q {
+                assert!(d(*a, *a) < 1e-15);
+                for b in &q {
+                    assert!(d(*a, *b) >= 0.0 && (d(*a, *b) - d(*b, *a)).abs() < 1e-15);
+                }
+            }
+        }
+        for d in metrics {
+            for a in &q {
+                for b in &q {
+                    for c in &q {
+                        assert!(d(*a, *c) <= d(*a, *b) + d(*b, *c) + 1e-14);
+                    }
+                }
+            }
+        }
+        let a = Quaternion::new(1.0, 0.0, 0.0, 0.0);
+        let b = Quaternion::from_axis_angle([0.0, 0.0, 1.0], 1.0);
+        let c = Quaternion::from_axis_angle([0.0, 0.0, 1.0], 2.0);
+        assert!(inner_product(a, c) > inner_product(a, b) + inner_product(b, c));
+    }
+}
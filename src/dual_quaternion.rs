@@ -0,0 +1,232 @@
+use std::ops::{Add, Sub, Mul};
+use std::fmt::{Display, Formatter, Result};
+use num_traits::Float;
+use crate::vector::{add, cross, dot, norm, scale, sub};
+use crate::{cast, Quaternion, UnitQuaternion};
+
+/// Dual quaternion `real + dual * epsilon` with `epsilon^2 = 0`.
+///
+/// A unit dual quaternion represents the rigid transform that rotates by `real` and
+/// then translates by `t`, with `dual = t * real / 2`.
+#[derive(PartialEq, PartialOrd, Copy, Clone, Debug)]
+pub struct DualQuaternion<T = f64> {
+    real: Quaternion<T>,
+    dual: Quaternion<T>,
+}
+
+impl<T: Float> DualQuaternion<T> {
+    pub fn new(real: Quaternion<T>, dual: Quaternion<T>) -> DualQuaternion<T> {
+        Self { real, dual }
+    }
+    pub fn identity() -> DualQuaternion<T> {
+        let zero = Quaternion::zero();
+        Self { real: Quaternion::identity(), dual: zero }
+    }
+    /// Rigid transform rotating by `rotation`, which is normalized, then translating by `translation`.
+    pub fn from_rotation_translation(rotation: Quaternion<T>, translation: [T; 3]) -> DualQuaternion<T> {
+        let real = rotation.unit();
+        let dual = Quaternion::from_vector(translation) * real * cast::<T>(0.5);
+        Self { real, dual }
+    }
+    pub fn from_translation(translation: [T; 3]) -> DualQuaternion<T> {
+        let real = Quaternion::identity();
+        DualQuaternion::from_rotation_translation(real, translation)
+    }
+    pub fn real(&self) -> Quaternion<T> {
+        self.real
+    }
+    pub fn dual(&self) -> Quaternion<T> {
+        self.dual
+    }
+    /// Rotation and translation of a unit dual quaternion.
+    pub fn to_rotation_translation(&self) -> (Quaternion<T>, [T; 3]) {
+        (self.real, self.translation())
+    }
+    pub fn translation(&self) -> [T; 3] {
+        (self.dual * self.real.conj() * cast::<T>(2.0)).vector()
+    }
+    /// Quaternion conjugate `real* + dual* epsilon`, the inverse of a unit dual quaternion.
+    pub fn conj(&self) -> DualQuaternion<T> {
+        Self { real: self.real.conj(), dual: self.dual.conj() }
+    }
+    /// Dual number conjugate `real - dual epsilon`.
+    pub fn dual_conj(&self) -> DualQuaternion<T> {
+        Self { real: self.real, dual: -self.dual }
+    }
+    /// Combined conjugate `real* - dual* epsilon`, used to transform points.
+    pub fn combined_conj(&self) -> DualQuaternion<T> {
+        Self { real: self.real.conj(), dual: -self.dual.conj() }
+    }
+    /// Multiplicative inverse; the real part must be non-zero.
+    pub fn inverse(&self) -> DualQuaternion<T> {
+        let real = self.real.inverse();
+        Self { real, dual: -(real * self.dual * real) }
+    }
+    /// Closest unit dual quaternion: unit real part and dual part orthogonal to it.
+    pub fn normalize(&self) -> DualQuaternion<T> {
+        let n = self.real.abs();
+        let real = self.real / n;
+        let dual = self.dual / n;
+        Self { real, dual: dual - real * real.dot(&dual) }
+    }
+    /// Transforms a point as `self * (1 + alpha epsilon) * self.combined_conj()`.
+    pub fn transform_point(&self, alpha: [T; 3]) -> [T; 3] {
+        let one = Quaternion::identity();
+        let p = DualQuaternion::new(one, Quaternion::from_vector(alpha));
+        (*self * p * self.combined_conj()).dual.vector()
+    }
+    /// Rotates a direction, ignoring the translation.
+    pub fn transform_vector(&self, alpha: [T; 3]) -> [T; 3] {
+        UnitQuaternion::new_unchecked(self.real).rotate_vector(alpha)
+    }
+    /// Screw parameters `(angle, displacement, axis, moment)` of a unit dual quaternion.
+    ///
+    /// The transform rotates by `angle` about the line with direction `axis` and moment
+    /// `moment` (passing through `cross(axis, moment)`), and translates by
+    /// `displacement` along it. A pure translation has zero angle and moment with the
+    /// axis along the translation; the identity uses the axis `[1, 0, 0]`.
+    pub fn to_screw(&self) -> (T, T, [T; 3], [T; 3]) {
+        let real = self.real.positive_hemisphere();
+        let t = self.translation();
+        let n = real.vector_abs();
+        if n <= T::epsilon() {
+            let d = norm(t);
+            let axis = if d == T::zero() { [T::one(), T::zero(), T::zero()] } else { scale(t, T::one() / d) };
+            return (T::zero(), d, axis, [T::zero(); 3]);
+        }
+        let angle = cast::<T>(2.0) * n.atan2(real.real());
+        let axis = scale(real.vector(), T::one() / n);
+        let d = dot(t, axis);
+        let perpendicular = sub(t, scale(axis, d));
+        let half = angle / cast(2.0);
+        let moment = scale(add(cross(t, axis), scale(perpendicular, half.cos() / half.sin())), cast(0.5));
+        (angle, d, axis, moment)
+    }
+    /// Unit dual quaternion from screw parameters, the inverse of `to_screw`.
+    pub fn from_screw(angle: T, displacement: T, axis: [T; 3], moment: [T; 3]) -> DualQuaternion<T> {
+        let rotation = Quaternion::from_axis_angle(axis, angle);
+        let point = cross(axis, moment);
+        let rotated = UnitQuaternion::new_unchecked(rotation).rotate_vector(point);
+        let translation = add(scale(axis, displacement), sub(point, rotated));
+        DualQuaternion::from_rotation_translation(rotation, translation)
+    }
+    /// Screw power: scales the rotation angle and displacement by `alpha`.
+    pub fn powf(&self, alpha: T) -> DualQuaternion<T> {
+        let (angle, displacement, axis, moment) = self.to_screw();
+        DualQuaternion::from_screw(angle * alpha, displacement * alpha, axis, moment)
+    }
+    /// Screw linear interpolation between unit dual quaternions along the shorter path,
+    /// `alpha * (alpha^-1 * beta)^t`.
+    pub fn sclerp(alpha: DualQuaternion<T>, beta: DualQuaternion<T>, t: T) -> DualQuaternion<T> {
+        let beta = if alpha.real.dot(&beta.real) < T::zero() {
+            DualQuaternion::new(-beta.real, -beta.dual)
+        } else {
+            beta
+        };
+        (alpha * (alpha.conj() * beta).powf(t)).normalize()
+    }
+}
+
+impl<T: Float> Add for DualQuaternion<T> {
+    type Output = DualQuaternion<T>;
+    fn add(self, alpha: DualQuaternion<T>) -> DualQuaternion<T> {
+        Self { real: self.real + alpha.real, dual: self.dual + alpha.dual }
+    }
+}
+
+impl<T: Float> Sub for DualQuaternion<T> {
+    type Output = DualQuaternion<T>;
+    fn sub(self, alpha: DualQuaternion<T>) -> DualQuaternion<T> {
+        Self { real: self.real - alpha.real, dual: self.dual - alpha.dual }
+    }
+}
+
+impl<T: Float> Mul for DualQuaternion<T> {
+    type Output = DualQuaternion<T>;
+    fn mul(self, alpha: DualQuaternion<T>) -> DualQuaternion<T> {
+        Self {
+            real: self.real * alpha.real,
+            dual: self.real * alpha.dual + self.dual * alpha.real,
+        }
+    }
+}
+
+impl<T: Float + Display> Display for DualQuaternion<T> {
+    fn fmt(&self, f: &mut Formatter) -> Result {
+        self.real.fmt(f)?;
+        write!(f, " + ")?;
+        self.dual.fmt(f)?;
+        write!(f, "e")
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::f64::consts::PI;
+    use super::DualQuaternion;
+    use crate::{Quaternion, UnitQuaternion};
+
+    fn assert_close(a: [f64; 3], b: [f64; 3]) {
+        for n in 0..3 {
+            assert!((a[n] - b[n]).abs() < 1e-12, "{:?} != {:?}", a, b);
+        }
+    }
+
+    fn transform() -> DualQuaternion {
+        DualQuaternion::from_rotation_translation(Quaternion::from_axis_angle([1.0, -2.0, 0.5], 1.2), [0.3, 4.0, -1.0])
+    }
+
+    #[test]
+    fn test_rotation_translation() {
+        let q = Quaternion::from_axis_angle([1.0, -2.0, 0.5], 1.2);
+        let dq = transform();
+        let (r, t) = dq.to_rotation_translation();
+        assert!((r - q).abs() < 1e-15);
+        assert_close(t, [0.3, 4.0, -1.0]);
+        let p = [2.0, -1.0, 0.7];
+        let rotated = UnitQuaternion::new(q).rotate_vector(p);
+        assert_close(dq.transform_point(p), [rotated[0] + 0.3, rotated[1] + 4.0, rotated[2] - 1.0]);
+        assert_close(dq.transform_vector(p), rotated);
+    }
+    #[test]
+    fn test_composition_inverse() {
+        let a = transform();
+        let b = DualQuaternion::from_rotation_translation(Quaternion::from_axis_angle([0.0, 0.0, 1.0], -0.4), [1.0, 0.0, 2.0]);
+        let p = [2.0, -1.0, 0.7];
+        assert_close((a * b).transform_point(p), a.transform_point(b.transform_point(p)));
+        assert_close(a.inverse().transform_point(a.transform_point(p)), p);
+        assert_close(a.conj().transform_point(a.transform_point(p)), p);
+        let scaled = DualQuaternion::new(a.real() * 3.0, a.dual() * 3.0);
+        assert_close((scaled * scaled.inverse()).transform_point(p), p);
+        assert_close(scaled.normalize().transform_point(p), a.transform_point(p));
+        let c = a.dual_conj();
+        assert_eq!((c.real(), c.dual()), (a.real(), -a.dual()));
+    }
+    #[test]
+    fn test_screw() {
+        let dq = transform();
+        let (angle, d, axis, moment) = dq.to_screw();
+        let p = [2.0, -1.0, 0.7];
+        assert_close(DualQuaternion::from_screw(angle, d, axis, moment).transform_point(p), dq.transform_point(p));
+        let half = dq.powf(0.5);
+        assert_close((half * half).transform_point(p), dq.transform_point(p));
+        let t = DualQuaternion::from_translation([0.0, 2.0, 0.0]);
+        let (angle, d, axis, _) = t.to_screw();
+        assert_eq!((angle, d), (0.0, 2.0));
+        assert_close(axis, [0.0, 1.0, 0.0]);
+    }
+    #[test]
+    fn test_sclerp() {
+        let a = DualQuaternion::from_rotation_translation(Quaternion::from_axis_angle([0.0, 0.0, 1.0], 0.0), [0.0, 0.0, 0.0]);
+        let b = DualQuaternion::from_rotation_translation(Quaternion::from_axis_angle([0.0, 0.0, 1.0], PI / 2.0), [0.0, 0.0, 4.0]);
+        let m = DualQuaternion::sclerp(a, b, 0.5);
+        let (r, t) = m.to_rotation_translation();
+        assert!((r - Quaternion::from_axis_angle([0.0, 0.0, 1.0], PI / 4.0)).abs() < 1e-12);
+        assert_close(t, [0.0, 0.0, 2.0]);
+        let p = [1.0, 2.0, 3.0];
+        assert_close(DualQuaternion::sclerp(a, b, 1.0).transform_point(p), b.transform_point(p));
+        let c = transform();
+        let flipped = DualQuaternion::new(-c.real(), -c.dual());
+        assert_close(DualQuaternion::sclerp(a, flipped, 0.5).transform_point(p), c.powf(0.5).transform_point(p));
+    }
+}
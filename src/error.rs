@@ -0,0 +1,137 @@
+use std::fmt::{Display, Formatter};
+use num_traits::Float;
+use crate::{Quaternion, UnitQuaternion};
+
+#[derive(PartialEq, Eq, Copy, Clone, Debug)]
+pub enum QuaternionError {
+    /// The norm is zero or too small to divide by.
+    ZeroNorm,
+    /// A component is infinite or NaN.
+    NonFinite,
+    /// The norm differs from one by more than the allowed tolerance.
+    NonUnit,
+    /// The input does not determine a unique result, e.g. too few or parallel vectors.
+    Degenerate,
+}
+
+impl Display for QuaternionError {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        match self {
+            QuaternionError::ZeroNorm => write!(f, "quaternion has zero norm"),
+            QuaternionError::NonFinite => write!(f, "quaternion has a non-finite component"),
+            QuaternionError::NonUnit => write!(f, "quaternion is not of unit norm"),
+            QuaternionError::Degenerate => write!(f, "input does not determine a unique quaternion"),
+        }
+    }
+}
+
+impl std::error::Error for QuaternionError {}
+
+impl<T: Float> Quaternion<T> {
+    /// Norm `|q|`, failing if `q` is zero or non-finite. A squared norm that
+    /// overflows or underflows is recomputed after scaling by the largest component.
+    fn check_invertible(&self) -> Result<T, QuaternionError> {
+        if !(self.i.is_finite() && self.j.is_finite() && self.k.is_finite() && self.l.is_finite()) {
+            return Err(QuaternionError::NonFinite);
+        }
+        let norm = self.dot(self);
+        if norm.is_normal() {
+            return Ok(norm.sqrt());
+        }
+        let s = self.i.abs().max(self.j.abs()).max(self.k.abs()).max(self.l.abs());
+        if s == T::zero() {
+            return Err(QuaternionError::ZeroNorm);
+        }
+        let scaled = *self / s;
+        Ok(scaled.dot(&scaled).sqrt() * s)
+    }
+    /// `unit`, failing for zero or non-finite quaternions instead of returning NaN.
+    pub fn try_unit(&self) -> Result<Quaternion<T>, QuaternionError> {
+        let norm = self.check_invertible()?;
+        Ok(*self / norm)
+    }
+    /// `inverse`, failing for zero or non-finite quaternions instead of returning NaN,
+    /// and with `ZeroNorm` for norms so small that the inverse overflows.
+    pub fn try_inverse(&self) -> Result<Quaternion<T>, QuaternionError> {
+        let norm = self.check_invertible()?;
+        let inverse = self.conj() / norm / norm;
+        if !(inverse.i.is_finite() && inverse.j.is_finite() && inverse.k.is_finite() && inverse.l.is_finite()) {
+            return Err(QuaternionError::ZeroNorm);
+        }
+        Ok(inverse)
+    }
+    /// Right division `self / alpha`, failing if `alpha` cannot be inverted.
+    pub fn checked_div(&self, alpha: &Quaternion<T>) -> Result<Quaternion<T>, QuaternionError> {
+        Ok(*self * alpha.try_inverse()?)
+    }
+}
+
+impl<T: Float> UnitQuaternion<T> {
+    /// `new`, failing for zero or non-finite quaternions.
+    pub fn try_new(alpha: Quaternion<T>) -> Result<UnitQuaternion<T>, QuaternionError> {
+        Ok(UnitQuaternion::new_unchecked(alpha.try_unit()?))
+    }
+    /// Wraps `alpha` without normalizing, failing unless its norm is within `eps` of one.
+    pub fn try_from_unit(alpha: Quaternion<T>, eps: T) -> Result<UnitQuaternion<T>, QuaternionError> {
+        alpha.check_invertible()?;
+        if (alpha.abs() - T::one()).abs() > eps {
+            return Err(QuaternionError::NonUnit);
+        }
+        Ok(UnitQuaternion::new_unchecked(alpha))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::QuaternionError;
+    use crate::{Quaternion, UnitQuaternion};
+
+    #[test]
+    fn test_checked() {
+        let zero = Quaternion::new(0.0, 0.0, 0.0, 0.0);
+        let nan = Quaternion::new(1.0, f64::NAN, 0.0, 0.0);
+        let q = Quaternion::new(0.0, 3.0, 0.0, -4.0);
+        assert_eq!(zero.try_unit(), Err(QuaternionError::ZeroNorm));
+        assert_eq!(Quaternion::new(-0.0, 0.0, -0.0, 0.0).try_inverse(), Err(QuaternionError::ZeroNorm));
+        assert_eq!(nan.try_unit(), Err(QuaternionError::NonFinite));
+        assert_eq!(q.try_unit(), Ok(Quaternion::new(0.0, 0.6, 0.0, -0.8)));
+        assert_eq!(q.try_inverse(), Ok(Quaternion::new(0.0, -0.12, 0.0, 0.16)));
+        assert_eq!(q.checked_div(&zero), Err(QuaternionError::ZeroNorm));
+        assert!(q.checked_div(&q).unwrap().approx_eq(&Quaternion::new(1.0, 0.0, 0.0, 0.0), 1e-15));
+    }
+    #[test]
+    fn test_large_magnitude() {
+        let q = Quaternion::new(1e200, 0.0, 0.0, 0.0);
+        assert_eq!(q.try_unit(), Ok(Quaternion::new(1.0, 0.0, 0.0, 0.0)));
+        assert_eq!(q.try_inverse(), Ok(Quaternion::new(1e-200, 0.0, 0.0, 0.0)));
+        let q = Quaternion::new(0.0, 3e300, 0.0, -4e300);
+        assert!(q.try_unit().unwrap().approx_eq(&Quaternion::new(0.0, 0.6, 0.0, -0.8), 1e-15));
+        assert!(q.try_inverse().unwrap().relative_eq(&Quaternion::new(0.0, -1.2e-301, 0.0, 1.6e-301), 0.0, 1e-15));
+        assert!(UnitQuaternion::try_new(q).is_ok());
+        let q = Quaternion::<f32>::new(3e19, 4e19, 0.0, 0.0);
+        assert!(q.try_unit().unwrap().approx_eq(&Quaternion::new(0.6, 0.8, 0.0, 0.0), 1e-6));
+    }
+    #[test]
+    fn test_small_magnitude() {
+        let q = Quaternion::new(1e-160, 0.0, 0.0, 0.0);
+        assert_eq!(q.try_unit(), Ok(Quaternion::new(1.0, 0.0, 0.0, 0.0)));
+        assert_eq!(q.try_inverse(), Ok(Quaternion::new(1e160, 0.0, 0.0, 0.0)));
+        let q = Quaternion::new(0.0, 3e-200, 0.0, -4e-200);
+        assert!(q.try_unit().unwrap().approx_eq(&Quaternion::new(0.0, 0.6, 0.0, -0.8), 1e-15));
+        assert!(q.try_inverse().unwrap().relative_eq(&Quaternion::new(0.0, -1.2e199, 0.0, 1.6e199), 0.0, 1e-15));
+        assert!(Quaternion::new(0.0, 0.0, 5e-324, 0.0).try_unit().is_ok());
+        assert_eq!(Quaternion::new(1e-200, 0.0, 0.0, 0.0).try_unit(), Ok(Quaternion::new(1.0, 0.0, 0.0, 0.0)));
+        assert_eq!(Quaternion::new(1e-310, 0.0, 0.0, 0.0).try_inverse(), Err(QuaternionError::ZeroNorm));
+        let q = Quaternion::<f32>::new(1e-20, 0.0, 0.0, 0.0);
+        assert_eq!(q.try_unit(), Ok(Quaternion::new(1.0, 0.0, 0.0, 0.0)));
+        assert!(UnitQuaternion::try_new(Quaternion::<f32>::new(0.0, 3e-25, 4e-25, 0.0)).is_ok());
+    }
+    #[test]
+    fn test_unit_quaternion() {
+        let q = Quaternion::new(0.0, 3.0, 0.0, -4.0);
+        assert_eq!(UnitQuaternion::try_new(q).map(|u| u.quaternion()), Ok(Quaternion::new(0.0, 0.6, 0.0, -0.8)));
+        assert_eq!(UnitQuaternion::try_from_unit(q, 1e-9), Err(QuaternionError::NonUnit));
+        assert!(UnitQuaternion::try_from_unit(q / 5.0, 1e-9).is_ok());
+        assert_eq!(QuaternionError::NonUnit.to_string(), "quaternion is not of unit norm");
+    }
+}
@@ -0,0 +1,198 @@
+use std::f64::consts::PI;
+use num_traits::Float;
+use crate::{cast, wrap_angle, Quaternion};
+
+/// Axis order of an Euler angle sequence: six Tait-Bryan and six proper Euler sequences.
+#[derive(PartialEq, Eq, Copy, Clone, Debug)]
+pub enum EulerAxes {
+    XYZ,
+    XZY,
+    YXZ,
+    YZX,
+    ZXY,
+    ZYX,
+    XYX,
+    XZX,
+    YXY,
+    YZY,
+    ZXZ,
+    ZYZ,
+}
+
+/// Euler angle convention.
+///
+/// `Intrinsic(XYZ)` with angles `(a, b, c)` rotates about the moving axes and equals
+/// `Rx(a) * Ry(b) * Rz(c)`. `Extrinsic(XYZ)` rotates about the fixed axes and equals
+/// `Rz(c) * Ry(b) * Rx(a)`.
+#[derive(PartialEq, Eq, Copy, Clone, Debug)]
+pub enum EulerSequence {
+    Intrinsic(EulerAxes),
+    Extrinsic(EulerAxes),
+}
+
+impl EulerAxes {
+    fn indices(&self) -> [usize; 3] {
+        match self {
+            EulerAxes::XYZ => [0, 1, 2],
+            EulerAxes::XZY => [0, 2, 1],
+            EulerAxes::YXZ => [1, 0, 2],
+            EulerAxes::YZX => [1, 2, 0],
+            EulerAxes::ZXY => [2, 0, 1],
+            EulerAxes::ZYX => [2, 1, 0],
+            EulerAxes::XYX => [0, 1, 0],
+            EulerAxes::XZX => [0, 2, 0],
+            EulerAxes::YXY => [1, 0, 1],
+            EulerAxes::YZY => [1, 2, 1],
+            EulerAxes::ZXZ => [2, 0, 2],
+            EulerAxes::ZYZ => [2, 1, 2],
+        }
+    }
+}
+
+fn elementary<T: Float>(axis: usize, angle: T) -> Quaternion<T> {
+    let mut alpha = [T::zero(); 3];
+    alpha[axis] = T::one();
+    Quaternion::from_axis_angle(alpha, angle)
+}
+
+impl<T: Float> Quaternion<T> {
+    /// Rotation composed from the Euler angles `alpha`, `beta`, `charlie` (radians),
+    /// applied about the first, second and third axis of `seq` respectively.
+    pub fn from_euler(seq: EulerSequence, alpha: T, beta: T, charlie: T) -> Quaternion<T> {
+        match seq {
+            EulerSequence::Intrinsic(axes) => {
+                let [a, b, c] = axes.indices();
+                elementary(a, alpha) * elementary(b, beta) * elementary(c, charlie)
+            }
+            EulerSequence::Extrinsic(axes) => {
+                let [a, b, c] = axes.indices();
+                elementary(c, charlie) * elementary(b, beta) * elementary(a, alpha)
+            }
+        }
+    }
+    /// Euler angles of `self` for `seq`, in the argument order of `from_euler`.
+    ///
+    /// The first and third angles lie in `(-pi, pi]`. The second lies in `[0, pi]` for
+    /// proper Euler sequences and in `[-pi/2, pi/2]` for Tait-Bryan sequences. The flag
+    /// is `true` in gimbal lock, when the first and third axes align and only their
+    /// sum (or difference) is determined; the third angle is then set to zero and the
+    /// first carries the whole rotation.
+    pub fn to_euler(&self, seq: EulerSequence) -> ([T; 3], bool) {
+        // Bernardes and Viollet, "Quaternion to Euler angles conversion: A direct,
+        // general and computationally efficient method", PLoS ONE, 2022.
+        let (axes, extrinsic) = match seq {
+            EulerSequence::Intrinsic(axes) => (axes, false),
+            EulerSequence::Extrinsic(axes) => (axes, true),
+        };
+        let [mut i, j, mut k] = axes.indices();
+        if !extrinsic {
+            std::mem::swap(&mut i, &mut k);
+        }
+        let symmetric = i == k;
+        if symmetric {
+            k = 3 - i - j;
+        }
+        let sign: T = if (i + 1) % 3 == j { T::one() } else { -T::one() };
+        let v = self.vector();
+        let (a, b, c, d) = if symmetric {
+            (self.i, v[i], v[j], v[k] * sign)
+        } else {
+            (self.i - v[j], v[i] + v[k] * sign, v[j] + self.i, v[k] * sign - v[i])
+        };
+        let (first, third) = if extrinsic { (0, 2) } else { (2, 0) };
+        let two: T = cast(2.0);
+        let mut angles = [T::zero(); 3];
+        angles[1] = two * c.hypot(d).atan2(a.hypot(b));
+        let threshold = T::epsilon().sqrt();
+        let half_sum = b.atan2(a);
+        let half_diff = d.atan2(c);
+        let singular = if angles[1].abs() <= threshold {
+            angles[0] = two * half_sum;
+            true
+        } else if (angles[1] - cast(PI)).abs() <= threshold {
+            angles[0] = if extrinsic { -two * half_diff } else { two * half_diff };
+            true
+        } else {
+            angles[first] = half_sum - half_diff;
+            angles[third] = half_sum + half_diff;
+            false
+        };
+        if !symmetric {
+            angles[third] = angles[third] * sign;
+            angles[1] = angles[1] - cast(PI / 2.0);
+        }
+        (angles.map(wrap_angle), singular)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{EulerAxes, EulerSequence};
+    use crate::Quaternion;
+
+    const AXES: [EulerAxes; 12] = [
+        EulerAxes::XYZ, EulerAxes::XZY, EulerAxes::YXZ, EulerAxes::YZX,
+        EulerAxes::ZXY, EulerAxes::ZYX, EulerAxes::XYX, EulerAxes::XZX,
+        EulerAxes::YXY, EulerAxes::YZY, EulerAxes::ZXZ, EulerAxes::ZYZ,
+    ];
+
+    fn same_rotation(a: Quaternion, b: Quaternion) -> bool {
+        let d = a.i * b.i + a.j * b.j + a.k * b.k + a.l * b.l;
+        (d.abs() - 1.0).abs() < 1e-10
+    }
+
+    fn is_symmetric(seq: EulerSequence) -> bool {
+        let (EulerSequence::Intrinsic(axes) | EulerSequence::Extrinsic(axes)) = seq;
+        axes.indices()[0] == axes.indices()[2]
+    }
+
+    fn sequences() -> Vec<EulerSequence> {
+        AXES.iter()
+            .flat_map(|a| [EulerSequence::Intrinsic(*a), EulerSequence::Extrinsic(*a)])
+            .collect()
+    }
+
+    #[test]
+    fn test_conventions() {
+        let (a, b, c) = (0.3, -0.7, 1.9);
+        let rx = Quaternion::from_axis_angle([1.0, 0.0, 0.0], a);
+        let ry = Quaternion::from_axis_angle([0.0, 1.0, 0.0], b);
+        let rz = Quaternion::from_axis_angle([0.0, 0.0, 1.0], c);
+        let q = Quaternion::from_euler(EulerSequence::Intrinsic(EulerAxes::XYZ), a, b, c);
+        assert!(same_rotation(q, rx * ry * rz));
+        let q = Quaternion::from_euler(EulerSequence::Extrinsic(EulerAxes::XYZ), a, b, c);
+        assert!(same_rotation(q, rz * ry * rx));
+    }
+    #[test]
+    fn test_round_trip() {
+        for seq in sequences() {
+            let symmetric = is_symmetric(seq);
+            let beta = if symmetric { 1.1 } else { -0.4 };
+            let q: Quaternion = Quaternion::from_euler(seq, 2.5, beta, -1.3);
+            let (angles, singular) = q.to_euler(seq);
+            assert!(!singular);
+            for (x, y) in angles.iter().zip([2.5f64, beta, -1.3]) {
+                assert!((x - y).abs() < 1e-12, "{:?}: {:?}", seq, angles);
+            }
+        }
+    }
+    #[test]
+    fn test_gimbal_lock() {
+        for seq in sequences() {
+            let symmetric = is_symmetric(seq);
+            let betas = if symmetric {
+                [0.0, std::f64::consts::PI]
+            } else {
+                [std::f64::consts::FRAC_PI_2, -std::f64::consts::FRAC_PI_2]
+            };
+            for beta in betas {
+                let q = Quaternion::from_euler(seq, 0.4, beta, 0.9);
+                let (angles, singular) = q.to_euler(seq);
+                assert!(singular, "{:?}", seq);
+                assert_eq!(angles[2], 0.0);
+                let r = Quaternion::from_euler(seq, angles[0], angles[1], angles[2]);
+                assert!(same_rotation(q, r), "{:?} {}: {:?}", seq, beta, angles);
+            }
+        }
+    }
+}
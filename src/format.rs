@@ -0,0 +1,148 @@
+use std::fmt::{Display, Formatter, LowerExp, Result, UpperExp};
+use num_traits::Float;
+use crate::{cast, Quaternion};
+
+type Component<T> = fn(&T, &mut Formatter) -> Result;
+
+/// Formats a value with `Component` under a formatter carrying only the width and
+/// precision.
+struct Plain<T>(T, Component<T>);
+
+impl<T> Display for Plain<T> {
+    fn fmt(&self, f: &mut Formatter) -> Result {
+        (self.1)(&self.0, f)
+    }
+}
+
+fn plain<T>(f: &mut Formatter, alpha: T, component: Component<T>) -> Result {
+    let width = f.width().unwrap_or(0);
+    match f.precision() {
+        Some(p) => write!(f, "{:w$.p$}", Plain(alpha, component), w = width, p = p),
+        None => write!(f, "{:w$}", Plain(alpha, component), w = width),
+    }
+}
+
+fn tuple<T>(f: &mut Formatter, components: &[T], component: Component<T>) -> Result {
+    write!(f, "(")?;
+    for (n, c) in components.iter().enumerate() {
+        if n > 0 {
+            write!(f, ", ")?;
+        }
+        component(c, f)?;
+    }
+    write!(f, ")")
+}
+
+/// `(w, x, y, z)`, or `w + xi + yj + zk` with the `#` flag. The remaining flags
+/// apply to every component. In the algebraic form the `x`, `y` and `z` terms only
+/// take the width and precision; sign, fill, alignment and zero padding affect `w` alone.
+fn quaternion<T: Float>(f: &mut Formatter, alpha: &Quaternion<T>, component: Component<T>) -> Result {
+    if !f.alternate() {
+        return tuple(f, &[alpha.i, alpha.j, alpha.k, alpha.l], component);
+    }
+    component(&alpha.i, f)?;
+    for (c, unit) in [(alpha.j, 'i'), (alpha.k, 'j'), (alpha.l, 'k')] {
+        write!(f, " {} ", if c.is_sign_negative() { '-' } else { '+' })?;
+        plain(f, c.abs(), component)?;
+        write!(f, "{}", unit)?;
+    }
+    Ok(())
+}
+
+impl<T: Float + Display> Display for Quaternion<T> {
+    fn fmt(&self, f: &mut Formatter) -> Result {
+        quaternion(f, self, Display::fmt)
+    }
+}
+
+impl<T: Float + LowerExp> LowerExp for Quaternion<T> {
+    fn fmt(&self, f: &mut Formatter) -> Result {
+        quaternion(f, self, LowerExp::fmt)
+    }
+}
+
+impl<T: Float + UpperExp> UpperExp for Quaternion<T> {
+    fn fmt(&self, f: &mut Formatter) -> Result {
+        quaternion(f, self, UpperExp::fmt)
+    }
+}
+
+/// Displays a quaternion as `(x, y, z, w)`. Created by `Quaternion::scalar_last`.
+pub struct ScalarLast<'a, T>(&'a Quaternion<T>);
+
+/// Displays the rotation of a quaternion as `angle° about (x, y, z)` with the
+/// angle in `[0, 180]` degrees. Created by `Quaternion::axis_angle_degrees`.
+pub struct AxisAngleDegrees<'a, T>(&'a Quaternion<T>);
+
+impl<T: Float + Display> Display for ScalarLast<'_, T> {
+    fn fmt(&self, f: &mut Formatter) -> Result {
+        let q = self.0;
+        tuple(f, &[q.j, q.k, q.l, q.i], Display::fmt)
+    }
+}
+
+impl<T: Float + Display> Display for AxisAngleDegrees<'_, T> {
+    fn fmt(&self, f: &mut Formatter) -> Result {
+        let (axis, angle) = self.0.to_axis_angle();
+        (angle * cast(180.0 / std::f64::consts::PI)).fmt(f)?;
+        write!(f, "° about ")?;
+        tuple(f, &axis, Display::fmt)
+    }
+}
+
+impl<T: Float> Quaternion<T> {
+    /// Adapter displaying `self` in scalar-last order `(x, y, z, w)`.
+    pub fn scalar_last(&self) -> ScalarLast<'_, T> {
+        ScalarLast(self)
+    }
+    /// Adapter displaying the rotation of `self` as an angle in degrees about a
+    /// unit axis.
+    pub fn axis_angle_degrees(&self) -> AxisAngleDegrees<'_, T> {
+        AxisAngleDegrees(self)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{DualQuaternion, Quaternion, Quaternion32, UnitQuaternion};
+
+    #[test]
+    fn test_display_flags() {
+        let q = Quaternion::new(1.0, -2.5, 0.125, 1234.5);
+        assert_eq!(format!("{}", q), "(1, -2.5, 0.125, 1234.5)");
+        assert_eq!(format!("{:.3}", q), "(1.000, -2.500, 0.125, 1234.500)");
+        assert_eq!(format!("{:+.1}", q), "(+1.0, -2.5, +0.1, +1234.5)");
+        assert_eq!(format!("{:e}", q), "(1e0, -2.5e0, 1.25e-1, 1.2345e3)");
+        assert_eq!(format!("{:.2E}", q), "(1.00E0, -2.50E0, 1.25E-1, 1.23E3)");
+        assert_eq!(format!("{:6.1}", Quaternion32::new(1.0, 2.0, 3.0, 4.0)), "(   1.0,    2.0,    3.0,    4.0)");
+        assert_eq!(format!("{:.1}", UnitQuaternion::<f64>::identity()), "(1.0, 0.0, 0.0, 0.0)");
+        let d = DualQuaternion::new(Quaternion::new(1.0, 0.0, 0.0, 0.0), Quaternion::new(0.0, 0.5, 0.0, 0.0));
+        assert_eq!(format!("{:.1}", d), "(1.0, 0.0, 0.0, 0.0) + (0.0, 0.5, 0.0, 0.0)e");
+    }
+    #[test]
+    fn test_display_algebraic() {
+        let q = Quaternion::new(1.0, -2.5, 0.125, -0.0);
+        assert_eq!(format!("{:#}", q), "1 - 2.5i + 0.125j - 0k");
+        assert_eq!(format!("{:+#.2}", q), "+1.00 - 2.50i + 0.12j - 0.00k");
+        assert_eq!(format!("{:#e}", q), "1e0 - 2.5e0i + 1.25e-1j - 0e0k");
+        assert_eq!(format!("{:#}", -q), "-1 + 2.5i - 0.125j + 0k");
+        assert_eq!(format!("{:#8.2}", q), "    1.00 -     2.50i +     0.12j -     0.00k");
+        assert_eq!(format!("{:+#06.1}", q), "+001.0 -    2.5i +    0.1j -    0.0k");
+        for q in [q, Quaternion::new(0.0, f64::INFINITY, 1e-7, -2.0)] {
+            for s in [format!("{}", q), format!("{:e}", q), format!("{:#}", q), format!("{:#e}", q)] {
+                assert_eq!(s.parse::<Quaternion>(), Ok(q), "{}", s);
+            }
+        }
+    }
+    #[test]
+    fn test_adapters() {
+        let q = Quaternion::new(1.0, -2.5, 0.125, 4.0);
+        assert_eq!(format!("{}", q.scalar_last()), "(-2.5, 0.125, 4, 1)");
+        assert_eq!(format!("{:+.1}", q.scalar_last()), "(-2.5, +0.1, +4.0, +1.0)");
+        let r = Quaternion::from_axis_angle([0.0, 0.0, 2.0], std::f64::consts::FRAC_PI_2);
+        assert_eq!(format!("{:.1}", r.axis_angle_degrees()), "90.0° about (0.0, 0.0, 1.0)");
+        assert_eq!(format!("{:.0}", (-r).axis_angle_degrees()), "90° about (0, 0, 1)");
+        let r = Quaternion::from_axis_angle([1.0, 1.0, 1.0], 2.0 * std::f64::consts::PI / 3.0);
+        assert_eq!(format!("{:.3}", r.axis_angle_degrees()), "120.000° about (0.577, 0.577, 0.577)");
+    }
+}
@@ -0,0 +1,99 @@
+use num_traits::Float;
+use crate::{cast, Quaternion, UnitQuaternion};
+
+fn shortest_arc<T: Float>(alpha: Quaternion<T>, beta: Quaternion<T>) -> Quaternion<T> {
+    if alpha.dot(&beta) < T::zero() {
+        -beta
+    } else {
+        beta
+    }
+}
+
+impl<T: Float> UnitQuaternion<T> {
+    /// Spherical linear interpolation from `alpha` (`t = 0`) to `beta` (`t = 1`) along
+    /// the shorter arc, at constant angular velocity. Falls back to `nlerp` when the
+    /// rotations are nearly identical.
+    pub fn slerp(alpha: UnitQuaternion<T>, beta: UnitQuaternion<T>, t: T) -> UnitQuaternion<T> {
+        let a = alpha.quaternion();
+        let delta = a.conj() * shortest_arc(a, beta.quaternion());
+        if delta.i > T::one() - T::epsilon().sqrt() {
+            return UnitQuaternion::nlerp(alpha, beta, t);
+        }
+        UnitQuaternion::new(a * delta.powf(t))
+    }
+    /// Normalized linear interpolation along the shorter arc. Cheaper than `slerp`
+    /// and follows the same path, but the angular velocity is not constant.
+    pub fn nlerp(alpha: UnitQuaternion<T>, beta: UnitQuaternion<T>, t: T) -> UnitQuaternion<T> {
+        let a = alpha.quaternion();
+        let b = shortest_arc(a, beta.quaternion());
+        UnitQuaternion::new(a * (T::one() - t) + b * t)
+    }
+    /// Steps from `alpha` towards `beta` by at most `max_angle` radians along the
+    /// shorter arc. Calling it once per time step with `omega * dt` moves at the
+    /// constant angular velocity `omega` until `beta` is reached.
+    pub fn rotate_towards(alpha: UnitQuaternion<T>, beta: UnitQuaternion<T>, max_angle: T) -> UnitQuaternion<T> {
+        let a = alpha.quaternion();
+        let delta = a.conj() * shortest_arc(a, beta.quaternion());
+        let angle = cast::<T>(2.0) * delta.vector_abs().atan2(delta.i);
+        if angle <= max_angle {
+            return beta;
+        }
+        UnitQuaternion::slerp(alpha, beta, max_angle / angle)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::f64::consts::PI;
+    use crate::{Quaternion, UnitQuaternion};
+
+    fn about_z(angle: f64) -> UnitQuaternion {
+        UnitQuaternion::new(Quaternion::from_axis_angle([0.0, 0.0, 1.0], angle))
+    }
+
+    fn assert_same_rotation(a: UnitQuaternion, b: UnitQuaternion) {
+        let d = a.quaternion().dot(&b.quaternion());
+        assert!((d.abs() - 1.0).abs() < 1e-12, "{} != {}", a, b);
+    }
+
+    #[test]
+    fn test_slerp() {
+        let a = about_z(0.2);
+        let b = about_z(1.4);
+        assert_same_rotation(UnitQuaternion::slerp(a, b, 0.0), a);
+        assert_same_rotation(UnitQuaternion::slerp(a, b, 1.0), b);
+        assert_same_rotation(UnitQuaternion::slerp(a, b, 0.25), about_z(0.5));
+        let c = UnitQuaternion::new(Quaternion::from_axis_angle([1.0, 2.0, -1.0], 2.0));
+        let d = UnitQuaternion::new(Quaternion::from_axis_angle([0.0, -1.0, 3.0], 1.0));
+        let m = UnitQuaternion::slerp(c, d, 0.5);
+        let angle = |p: UnitQuaternion, q: UnitQuaternion| (p.conj() * q).quaternion().to_axis_angle().1;
+        assert!((angle(c, m) - angle(m, d)).abs() < 1e-12);
+    }
+    #[test]
+    fn test_shortest_path() {
+        let a = about_z(0.1);
+        let b = UnitQuaternion::new(-about_z(-0.1).quaternion());
+        let m = UnitQuaternion::slerp(a, b, 0.5);
+        assert_same_rotation(m, UnitQuaternion::identity());
+        assert_same_rotation(UnitQuaternion::nlerp(a, b, 0.5), UnitQuaternion::identity());
+        let c = about_z(3.0 * PI / 2.0);
+        assert_same_rotation(UnitQuaternion::slerp(UnitQuaternion::identity(), c, 0.5), about_z(-PI / 4.0));
+    }
+    #[test]
+    fn test_nearly_identical() {
+        let a = about_z(1.0);
+        let b = about_z(1.0 + 1e-10);
+        let m = UnitQuaternion::slerp(a, b, 0.5);
+        assert!(m.quaternion().i.is_finite());
+        assert_same_rotation(m, about_z(1.0 + 5e-11));
+    }
+    #[test]
+    fn test_rotate_towards() {
+        let mut q = UnitQuaternion::identity();
+        let target = about_z(1.0);
+        for n in 1..=4 {
+            q = UnitQuaternion::rotate_towards(q, target, 0.3);
+            assert_same_rotation(q, about_z((0.3 * n as f64).min(1.0)));
+        }
+    }
+}
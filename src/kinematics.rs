@@ -0,0 +1,116 @@
+//! Quaternion kinematics for an attitude `q` rotating body-frame vectors into the
+//! world frame. Angular velocities are in radians per second.
+
+use num_traits::Float;
+use crate::vector::{add, scale};
+use crate::{cast, lie, Quaternion, UnitQuaternion};
+
+/// `dq/dt = q * omega / 2` for the body-frame angular velocity `omega`.
+pub fn derivative_body<T: Float>(q: Quaternion<T>, omega: [T; 3]) -> Quaternion<T> {
+    q * Quaternion::from_vector(omega) * cast::<T>(0.5)
+}
+
+/// `dq/dt = omega * q / 2` for the world-frame angular velocity `omega`.
+pub fn derivative_world<T: Float>(q: Quaternion<T>, omega: [T; 3]) -> Quaternion<T> {
+    Quaternion::from_vector(omega) * q * cast::<T>(0.5)
+}
+
+/// Constant body-frame angular velocity taking `q0` at time `t0` to `q1` at `t1`
+/// along the shorter arc.
+pub fn angular_velocity_body<T: Float>(q0: Quaternion<T>, t0: T, q1: Quaternion<T>, t1: T) -> [T; 3] {
+    let delta = lie::boxminus(UnitQuaternion::new(q1), UnitQuaternion::new(q0));
+    scale(delta, T::one() / (t1 - t0))
+}
+
+/// Constant world-frame angular velocity taking `q0` at time `t0` to `q1` at `t1`
+/// along the shorter arc.
+pub fn angular_velocity_world<T: Float>(q0: Quaternion<T>, t0: T, q1: Quaternion<T>, t1: T) -> [T; 3] {
+    let delta = lie::log(UnitQuaternion::new(q1) * UnitQuaternion::new(q0).inverse());
+    scale(delta, T::one() / (t1 - t0))
+}
+
+/// Zeroth-order integrator `q * exp(omega * dt)`, exact for a constant body rate.
+pub fn integrate_exponential<T: Float>(q: Quaternion<T>, omega: [T; 3], dt: T) -> Quaternion<T> {
+    (UnitQuaternion::new(q) * lie::exp(scale(omega, dt))).quaternion()
+}
+
+/// Classical fourth-order Runge-Kutta step for body rates `omega0` at the start and
+/// `omega1` at the end of the step, interpolated linearly, followed by normalization.
+pub fn integrate_rk4<T: Float>(q: Quaternion<T>, omega0: [T; 3], omega1: [T; 3], dt: T) -> Quaternion<T> {
+    let half: T = cast(0.5);
+    let middle = scale(add(omega0, omega1), half);
+    let k1 = derivative_body(q, omega0);
+    let k2 = derivative_body(q + k1 * (dt * half), middle);
+    let k3 = derivative_body(q + k2 * (dt * half), middle);
+    let k4 = derivative_body(q + k3 * dt, omega1);
+    (q + (k1 + k2 * cast::<T>(2.0) + k3 * cast::<T>(2.0) + k4) * (dt / cast(6.0))).unit()
+}
+
+/// Third-order Crouch-Grossman step, a product of exponentials that stays on the
+/// unit sphere, for body rates `omega0` and `omega1` interpolated linearly over the step.
+pub fn integrate_crouch_grossman<T: Float>(q: Quaternion<T>, omega0: [T; 3], omega1: [T; 3], dt: T) -> Quaternion<T> {
+    let stages: [(f64, f64); 3] = [(0.0, 13.0 / 51.0), (3.0 / 4.0, -2.0 / 3.0), (17.0 / 24.0, 24.0 / 17.0)];
+    let rate = |c: T| add(scale(omega0, T::one() - c), scale(omega1, c));
+    let q = stages
+        .iter()
+        .fold(UnitQuaternion::new(q), |q, (c, b)| q * lie::exp(scale(rate(cast(*c)), dt * cast(*b))));
+    q.quaternion()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn rate(t: f64) -> [f64; 3] {
+        [0.8, 0.5 - 1.2 * t, 0.3 + 0.9 * t]
+    }
+
+    type Integrator = fn(Quaternion, [f64; 3], [f64; 3], f64) -> Quaternion;
+
+    fn propagate(integrate: Integrator, steps: usize) -> Quaternion {
+        let dt = 2.0 / steps as f64;
+        (0..steps).fold(Quaternion::new(1.0, 0.0, 0.0, 0.0), |q, n| {
+            integrate(q, rate(n as f64 * dt), rate((n + 1) as f64 * dt), dt)
+        })
+    }
+
+    fn error(a: Quaternion, b: Quaternion) -> f64 {
+        (a.conj() * b).to_axis_angle().1
+    }
+
+    #[test]
+    fn test_derivatives() {
+        let q = Quaternion::from_axis_angle([1.0, 2.0, 0.0], 0.7);
+        let omega = [0.3, -0.2, 0.9];
+        let world = UnitQuaternion::new(q).rotate_vector(omega);
+        assert!(derivative_body(q, omega).approx_eq(&derivative_world(q, world), 1e-15));
+        let h = 1e-6;
+        let numeric = (integrate_exponential(q, omega, h) - integrate_exponential(q, omega, -h)) / (2.0 * h);
+        assert!(numeric.approx_eq(&derivative_body(q, omega), 1e-9));
+    }
+    #[test]
+    fn test_angular_velocity() {
+        let q0 = Quaternion::from_axis_angle([1.0, 2.0, 0.0], 0.7);
+        let omega = [0.3, -0.2, 0.9];
+        let q1 = -integrate_exponential(q0, omega, 0.5);
+        let body = angular_velocity_body(q0, 1.0, q1, 1.5);
+        assert!((0..3).all(|n| (body[n] - omega[n]).abs() < 1e-14), "{:?}", body);
+        let world = angular_velocity_world(q0, 1.0, q1, 1.5);
+        let expected = UnitQuaternion::new(q0).rotate_vector(omega);
+        assert!((0..3).all(|n| (world[n] - expected[n]).abs() < 1e-14), "{:?}", world);
+    }
+    #[test]
+    fn test_integrator_order() {
+        let exponential: Integrator = |q, omega0, _, dt| integrate_exponential(q, omega0, dt);
+        let reference = propagate(integrate_crouch_grossman, 20000);
+        let errors = |integrate: Integrator| (error(propagate(integrate, 100), reference), error(propagate(integrate, 200), reference));
+        let (e1, e2) = errors(exponential);
+        assert!(e1 / e2 > 1.8 && e1 / e2 < 2.2, "{} {}", e1, e2);
+        let (e1, e2) = errors(integrate_crouch_grossman);
+        assert!(e1 / e2 > 7.0 && e1 / e2 < 9.0, "{} {}", e1, e2);
+        let (e1, _) = errors(integrate_rk4);
+        assert!(e1 < 1e-6, "{}", e1);
+        assert!((propagate(integrate_rk4, 100).abs() - 1.0).abs() < 1e-15);
+        assert!((propagate(integrate_crouch_grossman, 100).abs() - 1.0).abs() < 1e-14);
+    }
+}
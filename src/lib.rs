@@ -1,16 +1,68 @@
-use std::ops::{Add, Sub, Mul};
-use std::fmt::{Display, Formatter, Result};
+use std::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign};
+use std::iter::{Product, Sum};
+use num_traits::Float;
+
+mod comparison;
+mod conversion;
+mod dual_quaternion;
+mod error;
+mod euler;
+mod format;
+mod interpolation;
+mod parse;
+mod swing_twist;
+mod transcendental;
+mod unit_quaternion;
+mod vector;
+
+pub mod ahrs;
+pub mod average;
+pub mod distance;
+pub mod kinematics;
+pub mod lie;
+pub mod mekf;
+pub mod registration;
+#[cfg(feature = "serde")]
+pub mod serialization;
+pub mod spline;
+pub mod wahba;
+
+pub use dual_quaternion::DualQuaternion;
+pub use error::QuaternionError;
+pub use euler::{EulerAxes, EulerSequence};
+pub use format::{AxisAngleDegrees, ScalarLast};
+pub use parse::{ParseErrorKind, ParseQuaternionError};
+pub use unit_quaternion::UnitQuaternion;
 
 #[derive(PartialEq, PartialOrd, Copy, Clone, Debug)]  
-pub struct Quaternion {
-    i: f64,
-    j: f64,
-    k: f64,
-    l: f64,
+pub struct Quaternion<T = f64> {
+    i: T,
+    j: T,
+    k: T,
+    l: T,
+}
+
+pub type Quaternion64 = Quaternion<f64>;
+pub type Quaternion32 = Quaternion<f32>;
+
+pub(crate) fn cast<T: Float>(alpha: f64) -> T {
+    T::from(alpha).unwrap()
 }
 
-impl Quaternion {
-    pub fn new(alpha: f64, beta: f64, charlie: f64, delta: f64) -> Quaternion {
+/// Maps an angle in `[-3 pi, 3 pi]` to `(-pi, pi]`.
+pub(crate) fn wrap_angle<T: Float>(alpha: T) -> T {
+    let pi: T = cast(std::f64::consts::PI);
+    if alpha <= -pi {
+        alpha + pi + pi
+    } else if alpha > pi {
+        alpha - pi - pi
+    } else {
+        alpha
+    }
+}
+
+impl<T: Float> Quaternion<T> {
+    pub fn new(alpha: T, beta: T, charlie: T, delta: T) -> Quaternion<T> {
         Self {
             i: alpha,
             j: beta,
@@ -18,7 +70,23 @@ impl Quaternion {
             l: delta,
         }
     }
-    pub fn conj(&self) -> Quaternion {
+    /// The multiplicative identity `(1, 0, 0, 0)`.
+    pub fn identity() -> Quaternion<T> {
+        Quaternion::new(T::one(), T::zero(), T::zero(), T::zero())
+    }
+    /// The additive identity `(0, 0, 0, 0)`.
+    pub fn zero() -> Quaternion<T> {
+        Quaternion::new(T::zero(), T::zero(), T::zero(), T::zero())
+    }
+    pub fn from_vector(alpha: [T; 3]) -> Quaternion<T> {
+        Quaternion::new(T::zero(), alpha[0], alpha[1], alpha[2])
+    }
+    /// `self` or `-self`, whichever has a non-negative real part. Both represent
+    /// the same rotation.
+    pub(crate) fn positive_hemisphere(&self) -> Quaternion<T> {
+        if self.i < T::zero() { -*self } else { *self }
+    }
+    pub fn conj(&self) -> Quaternion<T> {
         Self {
             i: self.i,
             j: -self.j,
@@ -26,7 +94,7 @@ impl Quaternion {
             l: -self.l,
         }
     }
-    pub fn grassman_product(delta: Quaternion, echo: Quaternion) -> Quaternion {
+    pub fn grassman_product(delta: Quaternion<T>, echo: Quaternion<T>) -> Quaternion<T> {
         Self {
             i: delta.i * echo.i - delta.j * echo.j - delta.k * echo.k - delta.l * echo.l,
             j: delta.i * echo.j + delta.j * echo.i + delta.k * echo.l - delta.l * echo.k,
@@ -34,48 +102,63 @@ impl Quaternion {
             l: delta.i * echo.l + delta.j * echo.k - delta.k * echo.j + delta.l * echo.i,
         }
     }
-    pub fn cross_product(delta: Quaternion, echo: Quaternion) -> Quaternion {
-        let b = Quaternion::new(0.5, 0.0, 0.0, 0.0);
+    pub fn cross_product(delta: Quaternion<T>, echo: Quaternion<T>) -> Quaternion<T> {
+        let b = Quaternion::new(cast(0.5), T::zero(), T::zero(), T::zero());
         let q = Quaternion::grassman_product(delta, echo) - Quaternion::grassman_product(echo, delta);
         Quaternion::grassman_product(b, q)
     }
-    pub fn real(&self) -> f64 {
-        return self.i;
+    pub fn real(&self) -> T {
+        self.i
     }
-    pub fn imag(&self) -> Vec<f64> {
-        let mut v: Vec<f64> = Vec::new();
-        v.push(self.j);
-        v.push(self.k);
-        v.push(self.l);
-        return v;
+    pub fn imag(&self) -> Vec<T> {
+        vec![self.j, self.k, self.l]
     }
-    pub fn abs(&self) -> f64 {
-        (self.i.powf(2.0) + self.j.powf(2.0) + self.k.powf(2.0) + self.l.powf(2.0)).powf(0.5)
+    pub fn vector(&self) -> [T; 3] {
+        [self.j, self.k, self.l]
     }
-    pub fn exchangeable(&self, alpha: &Quaternion) -> bool {
-        let q: Quaternion = Quaternion::cross_product(*self, *alpha);
-        if q.i == 0.0 && q.j == 0.0 && q.k == 0.0 && q.l == 0.0 {
-            return true
-        }
-        return false
-    } 
-    pub fn unit(&self) -> Quaternion {
+    pub fn vector_abs(&self) -> T {
+        (self.j.powi(2) + self.k.powi(2) + self.l.powi(2)).sqrt()
+    }
+    pub fn dot(&self, alpha: &Quaternion<T>) -> T {
+        self.i * alpha.i + self.j * alpha.j + self.k * alpha.k + self.l * alpha.l
+    }
+    pub fn abs(&self) -> T {
+        (self.i.powi(2) + self.j.powi(2) + self.k.powi(2) + self.l.powi(2)).sqrt()
+    }
+    /// Whether `self` and `alpha` commute, up to an absolute tolerance `eps` on the
+    /// components of their cross product.
+    pub fn exchangeable(&self, alpha: &Quaternion<T>, eps: T) -> bool {
+        let q: Quaternion<T> = Quaternion::cross_product(*self, *alpha);
+        q.approx_eq(&Quaternion::zero(), eps)
+    }
+    pub fn unit(&self) -> Quaternion<T> {
         Quaternion::divide_elementwise(self, self.abs())
     }
-    pub fn divide_elementwise(&self, div: f64) -> Quaternion {
-        let absdiv = div.abs();
+    /// Multiplicative inverse `conj / abs^2`.
+    pub fn inverse(&self) -> Quaternion<T> {
+        self.conj() / self.dot(self)
+    }
+    /// `alpha^-1 * self`, the solution `x` of `alpha * x = self`.
+    pub fn left_div(&self, alpha: &Quaternion<T>) -> Quaternion<T> {
+        alpha.inverse() * *self
+    }
+    /// `self * alpha^-1`, the solution `x` of `x * alpha = self`; same as `self / alpha`.
+    pub fn right_div(&self, alpha: &Quaternion<T>) -> Quaternion<T> {
+        *self * alpha.inverse()
+    }
+    pub fn divide_elementwise(&self, div: T) -> Quaternion<T> {
         Self {
-            i: self.i / absdiv,
-            j: self.j / absdiv,
-            k: self.k / absdiv,
-            l: self.l / absdiv,
+            i: self.i / div,
+            j: self.j / div,
+            k: self.k / div,
+            l: self.l / div,
         }
     }
 }
 
-impl Add for Quaternion {
-    type Output = Quaternion;
-    fn add(self, alpha: Quaternion) -> Quaternion {
+impl<T: Float> Add for Quaternion<T> {
+    type Output = Quaternion<T>;
+    fn add(self, alpha: Quaternion<T>) -> Quaternion<T> {
         Self {
             i: self.i + alpha.i,
             j: self.j + alpha.j,
@@ -85,9 +168,9 @@ impl Add for Quaternion {
     }
 }
 
-impl Sub for Quaternion {
-    type Output = Quaternion;
-    fn sub(self, alpha: Quaternion) -> Quaternion {
+impl<T: Float> Sub for Quaternion<T> {
+    type Output = Quaternion<T>;
+    fn sub(self, alpha: Quaternion<T>) -> Quaternion<T> {
         Self {
             i: self.i - alpha.i,
             j: self.j - alpha.j,
@@ -97,27 +180,171 @@ impl Sub for Quaternion {
     }
 }
 
-impl Mul for Quaternion {
-    type Output = Quaternion;
-    fn mul(self, alpha: Quaternion) -> Quaternion {
+impl<T: Float> Mul for Quaternion<T> {
+    type Output = Quaternion<T>;
+    fn mul(self, alpha: Quaternion<T>) -> Quaternion<T> {
+        Quaternion::grassman_product(self, alpha)
+    }
+}
+
+impl<T: Float> Div for Quaternion<T> {
+    type Output = Quaternion<T>;
+    fn div(self, alpha: Quaternion<T>) -> Quaternion<T> {
+        self.right_div(&alpha)
+    }
+}
+
+impl<T: Float> Neg for Quaternion<T> {
+    type Output = Quaternion<T>;
+    fn neg(self) -> Quaternion<T> {
         Self {
-            i: self.i * alpha.i - self.j * alpha.j - self.k * alpha.k - self.l * alpha.l,
-            j: self.i * alpha.j + self.j * alpha.i + self.k * alpha.l - self.l * alpha.k,
-            k: self.i * alpha.k - self.j * alpha.l + self.k * alpha.i + self.l * alpha.j,
-            l: self.i * alpha.l + self.j * alpha.k - self.k * alpha.j + self.l * alpha.i,
+            i: -self.i,
+            j: -self.j,
+            k: -self.k,
+            l: -self.l,
         }
     }
 }
 
-impl Display for Quaternion {
-    fn fmt(&self, f: &mut Formatter) -> Result {
-        write!(f, "({}, {}, {}, {})", self.i, self.j, self.k, self.l)
+impl<T: Float> Neg for &Quaternion<T> {
+    type Output = Quaternion<T>;
+    fn neg(self) -> Quaternion<T> {
+        -*self
+    }
+}
+
+impl<T: Float> Mul<T> for Quaternion<T> {
+    type Output = Quaternion<T>;
+    fn mul(self, alpha: T) -> Quaternion<T> {
+        Self {
+            i: self.i * alpha,
+            j: self.j * alpha,
+            k: self.k * alpha,
+            l: self.l * alpha,
+        }
+    }
+}
+
+impl<T: Float> Div<T> for Quaternion<T> {
+    type Output = Quaternion<T>;
+    fn div(self, alpha: T) -> Quaternion<T> {
+        Self {
+            i: self.i / alpha,
+            j: self.j / alpha,
+            k: self.k / alpha,
+            l: self.l / alpha,
+        }
+    }
+}
+
+macro_rules! scalar_mul {
+    ($($t:ty)*) => ($(
+        impl Mul<Quaternion<$t>> for $t {
+            type Output = Quaternion<$t>;
+            fn mul(self, alpha: Quaternion<$t>) -> Quaternion<$t> {
+                alpha * self
+            }
+        }
+
+        impl Mul<&Quaternion<$t>> for $t {
+            type Output = Quaternion<$t>;
+            fn mul(self, alpha: &Quaternion<$t>) -> Quaternion<$t> {
+                *alpha * self
+            }
+        }
+    )*)
+}
+
+scalar_mul! { f32 f64 }
+
+macro_rules! forward_ref_binop {
+    ($($imp:ident $method:ident $rhs:ty),*) => ($(
+        impl<T: Float> $imp<$rhs> for &Quaternion<T> {
+            type Output = Quaternion<T>;
+            fn $method(self, alpha: $rhs) -> Quaternion<T> {
+                $imp::$method(*self, alpha)
+            }
+        }
+
+        impl<T: Float> $imp<&$rhs> for Quaternion<T> {
+            type Output = Quaternion<T>;
+            fn $method(self, alpha: &$rhs) -> Quaternion<T> {
+                $imp::$method(self, *alpha)
+            }
+        }
+
+        impl<T: Float> $imp<&$rhs> for &Quaternion<T> {
+            type Output = Quaternion<T>;
+            fn $method(self, alpha: &$rhs) -> Quaternion<T> {
+                $imp::$method(*self, *alpha)
+            }
+        }
+    )*)
+}
+
+forward_ref_binop! {
+    Add add Quaternion<T>,
+    Sub sub Quaternion<T>,
+    Mul mul Quaternion<T>,
+    Div div Quaternion<T>,
+    Mul mul T,
+    Div div T
+}
+
+macro_rules! assign_op {
+    ($($imp:ident $method:ident $op:ident $op_method:ident $rhs:ty),*) => ($(
+        impl<T: Float> $imp<$rhs> for Quaternion<T> {
+            fn $method(&mut self, alpha: $rhs) {
+                *self = $op::$op_method(*self, alpha);
+            }
+        }
+
+        impl<T: Float> $imp<&$rhs> for Quaternion<T> {
+            fn $method(&mut self, alpha: &$rhs) {
+                *self = $op::$op_method(*self, *alpha);
+            }
+        }
+    )*)
+}
+
+assign_op! {
+    AddAssign add_assign Add add Quaternion<T>,
+    SubAssign sub_assign Sub sub Quaternion<T>,
+    MulAssign mul_assign Mul mul Quaternion<T>,
+    DivAssign div_assign Div div Quaternion<T>,
+    MulAssign mul_assign Mul mul T,
+    DivAssign div_assign Div div T
+}
+
+impl<T: Float> Sum for Quaternion<T> {
+    fn sum<I: Iterator<Item = Quaternion<T>>>(iter: I) -> Quaternion<T> {
+        iter.fold(Quaternion::zero(), |a, b| a + b)
+    }
+}
+
+impl<'a, T: Float> Sum<&'a Quaternion<T>> for Quaternion<T> {
+    fn sum<I: Iterator<Item = &'a Quaternion<T>>>(iter: I) -> Quaternion<T> {
+        iter.copied().sum()
+    }
+}
+
+/// Ordered product `q1 * q2 * ...`; the empty product is one.
+impl<T: Float> Product for Quaternion<T> {
+    fn product<I: Iterator<Item = Quaternion<T>>>(iter: I) -> Quaternion<T> {
+        iter.fold(Quaternion::identity(), |a, b| a * b)
+    }
+}
+
+impl<'a, T: Float> Product<&'a Quaternion<T>> for Quaternion<T> {
+    fn product<I: Iterator<Item = &'a Quaternion<T>>>(iter: I) -> Quaternion<T> {
+        iter.copied().product()
     }
 }
 
 #[cfg(test)]
 mod test {
-    use super::Quaternion;
+    use std::f64::consts::PI;
+    use super::{wrap_angle, Quaternion, Quaternion32, Quaternion64};
 
     #[test]
     fn test_basic_calculations() {
@@ -140,4 +367,63 @@ mod test {
         let q1 = Quaternion::new(14.0, -19.0, 9.0, -3.0);
         assert_eq!(q1.unit().abs(), 1.0);
     }
+    #[test]
+    fn test_divide_elementwise() {
+        let q1 = Quaternion::new(2.0, -4.0, 6.0, 1.0);
+        assert_eq!(q1.divide_elementwise(-2.0), Quaternion::new(-1.0, 2.0, -3.0, -0.5));
+        assert_eq!(q1.divide_elementwise(2.0), q1 / 2.0);
+    }
+    #[test]
+    fn test_generic_scalars() {
+        let q1: Quaternion32 = Quaternion::new(1.0, 2.0, 3.0, 4.0);
+        let q2: Quaternion64 = Quaternion::new(1.0, 2.0, 3.0, 4.0);
+        assert_eq!(q1 * q1.conj(), Quaternion::new(30.0f32, 0.0, 0.0, 0.0));
+        assert_eq!(q2 * q2.conj(), Quaternion::new(30.0f64, 0.0, 0.0, 0.0));
+        assert_eq!(Quaternion::new(1.0f32, 1.0, 1.0, 1.0).abs(), 2.0f32);
+    }
+    #[test]
+    fn test_operators() {
+        let q1 = Quaternion::new(1.0, 2.0, 3.0, 4.0);
+        let q2 = Quaternion::new(-0.5, 1.0, 0.0, 2.0);
+        assert_eq!(-q1, Quaternion::new(-1.0, -2.0, -3.0, -4.0));
+        assert_eq!(q1 * 2.0, Quaternion::new(2.0, 4.0, 6.0, 8.0));
+        assert_eq!(2.0 * q1, q1 * 2.0);
+        assert_eq!(q1 / 2.0, Quaternion::new(0.5, 1.0, 1.5, 2.0));
+        let (r1, r2) = (&q1, &q2);
+        assert_eq!(r1 * r2, q1 * q2);
+        assert_eq!(r1 + q2, q1 + q2);
+        assert_eq!(q1 - r2, q1 - q2);
+        assert_eq!(r1 / 2.0, q1 / 2.0);
+        assert!((q1 * q1.inverse() - Quaternion::new(1.0, 0.0, 0.0, 0.0)).abs() < 1e-15);
+        assert!(((q1 * q2) / q2 - q1).abs() < 1e-14);
+        assert!((q1.right_div(&q2) * q2 - q1).abs() < 1e-14);
+        assert!((q2 * q1.left_div(&q2) - q1).abs() < 1e-14);
+        let mut q = q1;
+        q += q2;
+        q -= q2;
+        q *= q2;
+        q /= q2;
+        q *= 3.0;
+        q /= 3.0;
+        assert!((q - q1).abs() < 1e-14);
+    }
+    #[test]
+    fn test_sum_product() {
+        let qs = [Quaternion::new(1.0, 2.0, 3.0, 4.0), Quaternion::new(-0.5, 1.0, 0.0, 2.0), Quaternion::new(0.0, 0.0, 1.0, 0.0)];
+        assert_eq!(qs.iter().sum::<Quaternion>(), qs[0] + qs[1] + qs[2]);
+        assert_eq!(qs.into_iter().sum::<Quaternion>(), qs[0] + qs[1] + qs[2]);
+        assert_eq!(qs.iter().product::<Quaternion>(), qs[0] * qs[1] * qs[2]);
+        assert_eq!(Vec::<Quaternion>::new().into_iter().product::<Quaternion>(), Quaternion::new(1.0, 0.0, 0.0, 0.0));
+        assert_eq!(Vec::<Quaternion>::new().into_iter().sum::<Quaternion>(), Quaternion::zero());
+        assert_eq!(qs[0] * Quaternion::identity(), qs[0]);
+        assert_eq!(Quaternion::new(-0.5, 1.0, 0.0, 2.0).positive_hemisphere(), Quaternion::new(0.5, -1.0, 0.0, -2.0));
+    }
+    #[test]
+    fn test_wrap_angle() {
+        assert_eq!(wrap_angle(-PI), PI);
+        assert_eq!(wrap_angle(PI), PI);
+        assert_eq!(wrap_angle(0.5), 0.5);
+        assert!((wrap_angle(1.5 * PI) + 0.5 * PI).abs() < 1e-15);
+        assert!((wrap_angle(-1.5 * PI) - 0.5 * PI).abs() < 1e-15);
+    }
 }
@@ -0,0 +1,143 @@
+//! The rotation group SO(3), represented by unit quaternions, and its tangent space.
+//!
+//! Tangent vectors are rotation vectors in so(3): direction is the rotation axis and
+//! norm the angle in radians. Perturbations are applied on the right, in the local
+//! frame: `boxplus(q, delta) = q * exp(delta)`.
+
+use num_traits::Float;
+use crate::vector::{hat, identity, mat_add, mat_mul, mat_scale, norm, scale};
+use crate::{cast, Quaternion, UnitQuaternion};
+
+/// Exponential map from a rotation vector to the rotation about it.
+pub fn exp<T: Float>(phi: [T; 3]) -> UnitQuaternion<T> {
+    UnitQuaternion::new(Quaternion::from_vector(scale(phi, cast(0.5))).exp())
+}
+
+/// Logarithmic map to the rotation vector of norm in `[0, pi]`.
+pub fn log<T: Float>(q: UnitQuaternion<T>) -> [T; 3] {
+    let q = q.quaternion().positive_hemisphere();
+    scale(q.ln().vector(), cast(2.0))
+}
+
+/// `q * exp(delta)`.
+pub fn boxplus<T: Float>(q: UnitQuaternion<T>, delta: [T; 3]) -> UnitQuaternion<T> {
+    q * exp(delta)
+}
+
+/// `log(b^-1 * a)`, the local difference satisfying `boxplus(b, boxminus(a, b)) == a`.
+pub fn boxminus<T: Float>(a: UnitQuaternion<T>, b: UnitQuaternion<T>) -> [T; 3] {
+    log(b.inverse() * a)
+}
+
+/// Adjoint, mapping local to global perturbations: `q * exp(v) = exp(adjoint(q) v) * q`.
+/// For SO(3) it is the rotation matrix of `q`.
+pub fn adjoint<T: Float>(q: UnitQuaternion<T>) -> [[T; 3]; 3] {
+    q.quaternion().to_rotation_matrix()
+}
+
+/// `I + a hat(phi) + b hat(phi)^2`.
+fn polynomial<T: Float>(phi: [T; 3], a: T, b: T) -> [[T; 3]; 3] {
+    let h = hat(phi);
+    mat_add(identity(), mat_add(mat_scale(h, a), mat_scale(mat_mul(h, h), b)))
+}
+
+/// Coefficients `(1 - cos t) / t^2` and `(t - sin t) / t^3` of the left Jacobian.
+fn jacobian_coefficients<T: Float>(theta: T) -> (T, T) {
+    if theta < T::epsilon().sqrt().sqrt() {
+        let t2 = theta * theta;
+        (cast::<T>(0.5) - t2 / cast(24.0), cast::<T>(1.0 / 6.0) - t2 / cast(120.0))
+    } else {
+        let t2 = theta * theta;
+        ((T::one() - theta.cos()) / t2, (theta - theta.sin()) / (t2 * theta))
+    }
+}
+
+/// Coefficient `1 / t^2 - (1 + cos t) / (2 t sin t)` of the inverse Jacobians.
+fn inverse_coefficient<T: Float>(theta: T) -> T {
+    if theta < T::epsilon().sqrt().sqrt() {
+        cast::<T>(1.0 / 12.0) + theta * theta / cast(720.0)
+    } else {
+        T::one() / (theta * theta) - (T::one() + theta.cos()) / (cast::<T>(2.0) * theta * theta.sin())
+    }
+}
+
+/// Left Jacobian: `exp(phi + d) ~ exp(left_jacobian(phi) d) * exp(phi)` for small `d`.
+pub fn left_jacobian<T: Float>(phi: [T; 3]) -> [[T; 3]; 3] {
+    let (a, b) = jacobian_coefficients(norm(phi));
+    polynomial(phi, a, b)
+}
+
+/// Right Jacobian: `exp(phi + d) ~ exp(phi) * exp(right_jacobian(phi) d)` for small `d`.
+pub fn right_jacobian<T: Float>(phi: [T; 3]) -> [[T; 3]; 3] {
+    let (a, b) = jacobian_coefficients(norm(phi));
+    polynomial(phi, -a, b)
+}
+
+/// Inverse of `left_jacobian`; singular at angles of `2 pi`.
+pub fn left_jacobian_inverse<T: Float>(phi: [T; 3]) -> [[T; 3]; 3] {
+    polynomial(phi, cast(-0.5), inverse_coefficient(norm(phi)))
+}
+
+/// Inverse of `right_jacobian`; singular at angles of `2 pi`.
+pub fn right_jacobian_inverse<T: Float>(phi: [T; 3]) -> [[T; 3]; 3] {
+    polynomial(phi, cast(0.5), inverse_coefficient(norm(phi)))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::vector::{add, mat_vec};
+
+    fn assert_close(a: [f64; 3], b: [f64; 3], eps: f64) {
+        for n in 0..3 {
+            assert!((a[n] - b[n]).abs() < eps, "{:?} != {:?}", a, b);
+        }
+    }
+
+    fn assert_identity(m: [[f64; 3]; 3]) {
+        for row in 0..3 {
+            let mut e = [0.0; 3];
+            e[row] = 1.0;
+            assert_close(m[row], e, 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_exp_log() {
+        for phi in [[0.3, -1.2, 0.8], [1e-10, 0.0, -2e-10], [0.0, 0.0, 0.0], [0.0, 3.1, 0.0]] {
+            assert_close(log(exp(phi)), phi, 1e-12);
+        }
+        let q = exp([0.0, 0.0, 1.0]);
+        assert!(q.quaternion().approx_eq(&Quaternion::from_axis_angle([0.0, 0.0, 1.0], 1.0), 1e-15));
+        assert_close(log(UnitQuaternion::new(-q.quaternion())), [0.0, 0.0, 1.0], 1e-15);
+    }
+    #[test]
+    fn test_boxplus_boxminus() {
+        let a = exp([0.3, -1.2, 0.8]);
+        let b = exp([-0.5, 0.1, 2.0]);
+        let d = boxminus(a, b);
+        assert!(boxplus(b, d).quaternion().rotation_eq(&a.quaternion(), 1e-14));
+        assert_close(boxminus(boxplus(a, [0.1, 0.2, -0.3]), a), [0.1, 0.2, -0.3], 1e-14);
+    }
+    #[test]
+    fn test_jacobians() {
+        let d = [1e-6, -2e-6, 1.5e-6];
+        for phi in [[0.3, -1.2, 0.8], [1e-6, 2e-6, 0.0]] {
+            let perturbed = exp(add(phi, d));
+            let right = exp(phi) * exp(mat_vec(right_jacobian(phi), d));
+            let left = exp(mat_vec(left_jacobian(phi), d)) * exp(phi);
+            assert_close(boxminus(perturbed, right), [0.0; 3], 1e-11);
+            assert_close(boxminus(perturbed, left), [0.0; 3], 1e-11);
+            assert_identity(mat_mul(left_jacobian(phi), left_jacobian_inverse(phi)));
+            assert_identity(mat_mul(right_jacobian(phi), right_jacobian_inverse(phi)));
+        }
+    }
+    #[test]
+    fn test_adjoint() {
+        let q = exp([0.3, -1.2, 0.8]);
+        let v = [0.2, 0.1, -0.4];
+        let a = q * exp(v);
+        let b = exp(mat_vec(adjoint(q), v)) * q;
+        assert!(a.quaternion().rotation_eq(&b.quaternion(), 1e-14));
+    }
+}
@@ -0,0 +1,199 @@
+//! Multiplicative extended Kalman filter for attitude and gyroscope bias.
+//!
+//! The attitude rotates body-frame vectors into the reference frame. The error state
+//! is `[dtheta, dbias]`, where the true attitude is `boxplus(attitude, dtheta)` as in
+//! the `lie` module, so its covariance is expressed in the body frame.
+
+use num_traits::Float;
+use crate::vector::{hat, identity, inverse3, mat_add, mat_mul, mat_scale, mat_vec, scale, sub, transpose};
+use crate::{lie, Quaternion, UnitQuaternion};
+
+fn diagonal<T: Float>(alpha: T, beta: T) -> [[T; 6]; 6] {
+    let mut m = [[T::zero(); 6]; 6];
+    for n in 0..3 {
+        m[n][n] = alpha;
+        m[n + 3][n + 3] = beta;
+    }
+    m
+}
+
+#[derive(Copy, Clone, Debug)]
+pub struct Mekf<T = f64> {
+    /// Gyroscope white noise density, in radians per second per square root hertz.
+    pub gyro_noise: T,
+    /// Gyroscope bias random walk density, in radians per second squared per square
+    /// root hertz.
+    pub bias_noise: T,
+    attitude: UnitQuaternion<T>,
+    bias: [T; 3],
+    covariance: [[T; 6]; 6],
+}
+
+impl<T: Float> Mekf<T> {
+    /// Filter starting at `attitude` with zero bias, and initial standard deviations
+    /// `attitude_sigma` (radians) and `bias_sigma` (radians per second).
+    pub fn new(attitude: Quaternion<T>, attitude_sigma: T, bias_sigma: T, gyro_noise: T, bias_noise: T) -> Mekf<T> {
+        Self {
+            gyro_noise,
+            bias_noise,
+            attitude: UnitQuaternion::new(attitude),
+            bias: [T::zero(); 3],
+            covariance: diagonal(attitude_sigma * attitude_sigma, bias_sigma * bias_sigma),
+        }
+    }
+    pub fn attitude(&self) -> Quaternion<T> {
+        self.attitude.quaternion()
+    }
+    pub fn bias(&self) -> [T; 3] {
+        self.bias
+    }
+    /// Error state covariance, attitude block first.
+    pub fn covariance(&self) -> [[T; 6]; 6] {
+        self.covariance
+    }
+    /// Propagates over `dt` seconds with a gyroscope sample in radians per second.
+    pub fn propagate(&mut self, gyro: [T; 3], dt: T) {
+        let phi = scale(sub(gyro, self.bias), dt);
+        let rotation = lie::exp(phi);
+        let attitude_block = transpose(lie::adjoint(rotation));
+        let bias_block = mat_scale(lie::right_jacobian(phi), -dt);
+        let mut transition = diagonal(T::zero(), T::one());
+        for row in 0..3 {
+            for col in 0..3 {
+                transition[row][col] = attitude_block[row][col];
+                transition[row][col + 3] = bias_block[row][col];
+            }
+        }
+        let noise = diagonal(self.gyro_noise * self.gyro_noise * dt, self.bias_noise * self.bias_noise * dt);
+        let propagated = mat_mul(mat_mul(transition, self.covariance), transpose(transition));
+        self.covariance = mat_add(propagated, noise);
+        self.attitude = self.attitude * rotation;
+    }
+    /// Updates with a body-frame measurement `measured` of the reference-frame vector
+    /// `reference`, with isotropic noise of standard deviation `sigma`. Both vectors
+    /// should have the same length, typically unit.
+    pub fn update_vector(&mut self, measured: [T; 3], reference: [T; 3], sigma: T) {
+        let predicted = self.attitude.inverse_rotate_vector(reference);
+        let residual = sub(measured, predicted);
+        let mut observation = [[T::zero(); 6]; 3];
+        let h = hat(predicted);
+        for row in 0..3 {
+            observation[row][..3].copy_from_slice(&h[row]);
+        }
+        let noise = mat_scale(identity(), sigma * sigma);
+        let cross_covariance = mat_mul(self.covariance, transpose(observation));
+        let innovation = mat_add(mat_mul(observation, cross_covariance), noise);
+        let Some(innovation_inverse) = inverse3(innovation) else {
+            return;
+        };
+        let gain = mat_mul(cross_covariance, innovation_inverse);
+        let correction = mat_vec(gain, residual);
+        // Joseph form keeps the covariance symmetric and positive definite.
+        let factor = mat_add(diagonal(T::one(), T::one()), mat_scale(mat_mul(gain, observation), -T::one()));
+        let joseph = mat_mul(mat_mul(factor, self.covariance), transpose(factor));
+        self.covariance = mat_add(joseph, mat_mul(mat_mul(gain, noise), transpose(gain)));
+        self.attitude = lie::boxplus(self.attitude, [correction[0], correction[1], correction[2]]);
+        for n in 0..3 {
+            self.bias[n] = self.bias[n] + correction[n + 3];
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Mekf;
+    use crate::{lie, Quaternion, UnitQuaternion};
+
+    /// Deterministic xorshift generator with Box-Muller normal samples.
+    struct Noise(u64);
+
+    impl Noise {
+        fn uniform(&mut self) -> f64 {
+            self.0 ^= self.0 << 13;
+            self.0 ^= self.0 >> 7;
+            self.0 ^= self.0 << 17;
+            (self.0 >> 11) as f64 / (1u64 << 53) as f64
+        }
+        fn normal(&mut self, sigma: f64) -> f64 {
+            let (a, b) = (self.uniform().max(1e-300), self.uniform());
+            sigma * (-2.0 * a.ln()).sqrt() * (2.0 * std::f64::consts::PI * b).cos()
+        }
+        fn vector(&mut self, sigma: f64) -> [f64; 3] {
+            [self.normal(sigma), self.normal(sigma), self.normal(sigma)]
+        }
+    }
+
+    struct Simulation {
+        truth: Quaternion,
+        bias: [f64; 3],
+        noise: Noise,
+    }
+
+    const DT: f64 = 0.01;
+    const GYRO_NOISE: f64 = 1e-3;
+    const VECTOR_NOISE: f64 = 0.01;
+    const REFERENCES: [[f64; 3]; 2] = [[0.0, 0.0, 1.0], [0.6, 0.0, -0.8]];
+
+    impl Simulation {
+        fn rate(step: usize) -> [f64; 3] {
+            let t = step as f64 * DT;
+            [0.3 * (0.5 * t).sin(), 0.2 * (0.3 * t).cos(), 0.1]
+        }
+        /// Advances the truth and returns the gyroscope sample.
+        fn step(&mut self, step: usize) -> [f64; 3] {
+            let rate = Simulation::rate(step);
+            self.truth *= lie::exp([rate[0] * DT, rate[1] * DT, rate[2] * DT]).quaternion();
+            let white = self.noise.vector(GYRO_NOISE / DT.sqrt());
+            [rate[0] + self.bias[0] + white[0], rate[1] + self.bias[1] + white[1], rate[2] + self.bias[2] + white[2]]
+        }
+        fn measure(&mut self, reference: [f64; 3]) -> [f64; 3] {
+            let body = UnitQuaternion::new(self.truth).inverse_rotate_vector(reference);
+            let white = self.noise.vector(VECTOR_NOISE);
+            [body[0] + white[0], body[1] + white[1], body[2] + white[2]]
+        }
+    }
+
+    fn run(filter: &mut Mekf, simulation: &mut Simulation, steps: usize) {
+        for step in 0..steps {
+            let gyro = simulation.step(step);
+            filter.propagate(gyro, DT);
+            if step % 10 == 9 {
+                for reference in REFERENCES {
+                    let measured = simulation.measure(reference);
+                    filter.update_vector(measured, reference, VECTOR_NOISE);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_converges_with_bias() {
+        let truth = Quaternion::from_axis_angle([1.0, -0.5, 0.2], 1.0);
+        let mut simulation = Simulation { truth, bias: [0.01, -0.02, 0.005], noise: Noise(0x2545f4914f6cdd1d) };
+        let start = truth * Quaternion::from_axis_angle([0.0, 1.0, 1.0], 0.3);
+        let mut filter = Mekf::new(start, 0.5, 0.05, GYRO_NOISE, 1e-5);
+        run(&mut filter, &mut simulation, 6000);
+        let error = lie::boxminus(UnitQuaternion::new(simulation.truth), UnitQuaternion::new(filter.attitude()));
+        let p = filter.covariance();
+        for n in 0..3 {
+            assert!(error[n].abs() < 3.0 * p[n][n].sqrt() + 1e-4, "{:?} {:?}", error, p[n][n]);
+            assert!(error[n].abs() < 5e-3, "{:?}", error);
+            assert!((filter.bias()[n] - simulation.bias[n]).abs() < 1e-3, "{:?}", filter.bias());
+        }
+    }
+    #[test]
+    fn test_propagation_only() {
+        let truth = Quaternion::new(1.0, 0.0, 0.0, 0.0);
+        let mut simulation = Simulation { truth, bias: [0.0; 3], noise: Noise(7) };
+        let mut filter = Mekf::new(truth, 1e-3, 0.0, GYRO_NOISE, 0.0);
+        for step in 0..1000 {
+            let rate = Simulation::rate(step);
+            simulation.step(step);
+            filter.propagate(rate, DT);
+        }
+        assert!(filter.attitude().rotation_eq(&simulation.truth, 1e-12));
+        let p = filter.covariance();
+        let expected = 1e-6 + GYRO_NOISE * GYRO_NOISE * 10.0;
+        assert!((p[0][0] - expected).abs() < 1e-9 && p[3][3] == 0.0, "{:?}", p);
+    }
+}
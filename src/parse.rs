@@ -0,0 +1,269 @@
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+use num_traits::Float;
+use crate::Quaternion;
+
+#[derive(PartialEq, Eq, Copy, Clone, Debug)]
+pub enum ParseErrorKind {
+    /// The input is empty or only whitespace.
+    Empty,
+    /// The input ended where more was expected.
+    UnexpectedEnd,
+    /// A character that cannot appear at this point.
+    UnexpectedCharacter(char),
+    /// A number that the scalar type cannot parse.
+    InvalidNumber,
+    /// A second term for the same component, e.g. `2i + 3i`. `None` stands for
+    /// the real part.
+    DuplicateTerm(Option<char>),
+}
+
+/// Error returned by `Quaternion::from_str`, with the byte offset into the input
+/// at which parsing failed.
+#[derive(PartialEq, Eq, Copy, Clone, Debug)]
+pub struct ParseQuaternionError {
+    kind: ParseErrorKind,
+    position: usize,
+}
+
+impl ParseQuaternionError {
+    pub fn kind(&self) -> ParseErrorKind {
+        self.kind
+    }
+    pub fn position(&self) -> usize {
+        self.position
+    }
+}
+
+impl Display for ParseQuaternionError {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        match self.kind {
+            ParseErrorKind::Empty => write!(f, "cannot parse quaternion from empty string"),
+            ParseErrorKind::UnexpectedEnd => write!(f, "unexpected end of input at position {}", self.position),
+            ParseErrorKind::UnexpectedCharacter(c) => write!(f, "unexpected character '{}' at position {}", c, self.position),
+            ParseErrorKind::InvalidNumber => write!(f, "invalid number at position {}", self.position),
+            ParseErrorKind::DuplicateTerm(Some(c)) => write!(f, "duplicate '{}' term at position {}", c, self.position),
+            ParseErrorKind::DuplicateTerm(None) => write!(f, "duplicate real term at position {}", self.position),
+        }
+    }
+}
+
+impl std::error::Error for ParseQuaternionError {}
+
+struct Parser<'a> {
+    input: &'a str,
+    position: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn error<R>(&self, kind: ParseErrorKind, position: usize) -> Result<R, ParseQuaternionError> {
+        Err(ParseQuaternionError { kind, position })
+    }
+    fn peek(&self) -> Option<char> {
+        self.input[self.position..].chars().next()
+    }
+    fn unexpected<R>(&self) -> Result<R, ParseQuaternionError> {
+        match self.peek() {
+            Some(c) => self.error(ParseErrorKind::UnexpectedCharacter(c), self.position),
+            None => self.error(ParseErrorKind::UnexpectedEnd, self.position),
+        }
+    }
+    fn skip_whitespace(&mut self) {
+        let rest = &self.input[self.position..];
+        self.position += rest.len() - rest.trim_start().len();
+    }
+    fn eat(&mut self, c: char) -> bool {
+        if self.peek() == Some(c) {
+            self.position += c.len_utf8();
+            true
+        } else {
+            false
+        }
+    }
+    fn expect(&mut self, c: char) -> Result<(), ParseQuaternionError> {
+        self.skip_whitespace();
+        if self.eat(c) { Ok(()) } else { self.unexpected() }
+    }
+    fn digits(&mut self) -> usize {
+        let rest = &self.input[self.position..];
+        let n = rest.len() - rest.trim_start_matches(|c: char| c.is_ascii_digit()).len();
+        self.position += n;
+        n
+    }
+    /// Length of a case-insensitive `inf`, `infinity` or `nan` at the current position.
+    fn special(&self) -> usize {
+        let rest = &self.input.as_bytes()[self.position..];
+        ["infinity", "inf", "nan"]
+            .iter()
+            .find(|word| rest.len() >= word.len() && rest[..word.len()].eq_ignore_ascii_case(word.as_bytes()))
+            .map_or(0, |word| word.len())
+    }
+    fn starts_number(&self) -> bool {
+        matches!(self.peek(), Some(c) if c.is_ascii_digit() || c == '.') || self.special() > 0
+    }
+    /// Unsigned decimal number with optional fraction and exponent, or `inf`/`nan`.
+    fn number<T: FromStr>(&mut self) -> Result<T, ParseQuaternionError> {
+        let start = self.position;
+        let special = self.special();
+        if special > 0 {
+            self.position += special;
+        } else {
+            let mut n = self.digits();
+            if self.eat('.') {
+                n += self.digits();
+            }
+            if n == 0 {
+                self.position = start;
+                return self.unexpected();
+            }
+            let mantissa = self.position;
+            if self.eat('e') || self.eat('E') {
+                let _ = self.eat('+') || self.eat('-');
+                if self.digits() == 0 {
+                    self.position = mantissa;
+                }
+            }
+        }
+        match self.input[start..self.position].parse() {
+            Ok(value) => Ok(value),
+            Err(_) => self.error(ParseErrorKind::InvalidNumber, start),
+        }
+    }
+    fn signed_number<T: Float + FromStr>(&mut self) -> Result<T, ParseQuaternionError> {
+        self.skip_whitespace();
+        let negative = self.eat('-');
+        if !negative {
+            self.eat('+');
+        }
+        let value: T = self.number()?;
+        Ok(if negative { -value } else { value })
+    }
+    /// `(w, x, y, z)`, the form written by `Display`.
+    fn tuple<T: Float + FromStr>(&mut self) -> Result<Quaternion<T>, ParseQuaternionError> {
+        self.expect('(')?;
+        let mut components = [T::zero(); 4];
+        for (n, component) in components.iter_mut().enumerate() {
+            if n > 0 {
+                self.expect(',')?;
+            }
+            *component = self.signed_number()?;
+        }
+        self.expect(')')?;
+        let [w, x, y, z] = components;
+        Ok(Quaternion::new(w, x, y, z))
+    }
+    /// Sum of terms like `1`, `- 2.5i`, `+j` or `3e-2k` in any order.
+    fn algebraic<T: Float + FromStr>(&mut self) -> Result<Quaternion<T>, ParseQuaternionError> {
+        let mut components: [Option<T>; 4] = [None; 4];
+        loop {
+            self.skip_whitespace();
+            if self.peek().is_none() {
+                break;
+            }
+            let first = components.iter().all(Option::is_none);
+            let start = self.position;
+            let negative = if self.eat('-') {
+                true
+            } else if self.eat('+') || first {
+                false
+            } else {
+                return self.unexpected();
+            };
+            self.skip_whitespace();
+            let value = if self.starts_number() { Some(self.number::<T>()?) } else { None };
+            let unit = match self.peek() {
+                Some(c @ ('i' | 'j' | 'k')) => {
+                    self.position += 1;
+                    Some(c)
+                }
+                _ => None,
+            };
+            let index = match unit {
+                Some('i') => 1,
+                Some('j') => 2,
+                Some('k') => 3,
+                _ if value.is_none() => return self.unexpected(),
+                _ => 0,
+            };
+            if components[index].is_some() {
+                return self.error(ParseErrorKind::DuplicateTerm(unit), start);
+            }
+            let value = value.unwrap_or_else(T::one);
+            components[index] = Some(if negative { -value } else { value });
+        }
+        let [w, x, y, z] = components.map(|c| c.unwrap_or_else(T::zero));
+        Ok(Quaternion::new(w, x, y, z))
+    }
+}
+
+impl<T: Float + FromStr> FromStr for Quaternion<T> {
+    type Err = ParseQuaternionError;
+
+    /// Parses the `Display` form `(w, x, y, z)` or algebraic notation such as
+    /// `1 + 2i - 3j + 4.5k`, whose terms may come in any order or be omitted.
+    fn from_str(s: &str) -> Result<Quaternion<T>, ParseQuaternionError> {
+        let mut parser = Parser { input: s, position: 0 };
+        parser.skip_whitespace();
+        if parser.peek().is_none() {
+            return parser.error(ParseErrorKind::Empty, 0);
+        }
+        let q = if parser.peek() == Some('(') { parser.tuple()? } else { parser.algebraic()? };
+        parser.skip_whitespace();
+        if parser.peek().is_some() {
+            return parser.unexpected();
+        }
+        Ok(q)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{ParseErrorKind, ParseQuaternionError};
+    use crate::{Quaternion, Quaternion32};
+
+    fn error(s: &str) -> (ParseErrorKind, usize) {
+        let e: ParseQuaternionError = s.parse::<Quaternion>().unwrap_err();
+        (e.kind(), e.position())
+    }
+
+    #[test]
+    fn test_parse_tuple() {
+        let q = Quaternion::new(1.5, -2.0, 3e-20, -0.125);
+        assert_eq!(q.to_string().parse::<Quaternion>().unwrap(), q);
+        let q: Quaternion32 = " ( 1 ,+2.5, -3E2,4 ) ".parse().unwrap();
+        assert_eq!(q, Quaternion::new(1.0, 2.5, -300.0, 4.0));
+        let q: Quaternion = "(inf, -inf, 0, 1)".parse().unwrap();
+        assert_eq!(q, Quaternion::new(f64::INFINITY, f64::NEG_INFINITY, 0.0, 1.0));
+        assert!("(NaN, 0, 0, 0)".parse::<Quaternion>().unwrap().real().is_nan());
+    }
+    #[test]
+    fn test_parse_algebraic() {
+        let q = Quaternion::new(1.0, 2.0, -3.0, 4.5);
+        for s in ["1 + 2i - 3j + 4.5k", "4.5k+1-3j+2i", "  +1+2i-3j+4.5k  "] {
+            assert_eq!(s.parse::<Quaternion>(), Ok(q), "{}", s);
+        }
+        assert_eq!("-j".parse::<Quaternion>(), Ok(Quaternion::new(0.0, 0.0, -1.0, 0.0)));
+        assert_eq!("2.5".parse::<Quaternion>(), Ok(Quaternion::new(2.5, 0.0, 0.0, 0.0)));
+        assert_eq!("i - k".parse::<Quaternion>(), Ok(Quaternion::new(0.0, 1.0, 0.0, -1.0)));
+        assert_eq!("1e3 - 2.5E-2i + .5j".parse::<Quaternion>(), Ok(Quaternion::new(1000.0, -0.025, 0.5, 0.0)));
+        assert_eq!(error("- inf k"), (ParseErrorKind::UnexpectedCharacter('k'), 6));
+        assert_eq!("-infk".parse::<Quaternion>().map(|q| q.vector()[2]), Ok(f64::NEG_INFINITY));
+    }
+    #[test]
+    fn test_parse_errors() {
+        assert_eq!(error(""), (ParseErrorKind::Empty, 0));
+        assert_eq!(error("   "), (ParseErrorKind::Empty, 0));
+        assert_eq!(error("(1, 2, 3)"), (ParseErrorKind::UnexpectedCharacter(')'), 8));
+        assert_eq!(error("(1, 2, 3, 4"), (ParseErrorKind::UnexpectedEnd, 11));
+        assert_eq!(error("(1, 2, x, 4)"), (ParseErrorKind::UnexpectedCharacter('x'), 7));
+        assert_eq!(error("1 + 2i 3j"), (ParseErrorKind::UnexpectedCharacter('3'), 7));
+        assert_eq!(error("1 + 2i +"), (ParseErrorKind::UnexpectedEnd, 8));
+        assert_eq!(error("1 + 2i - 3i"), (ParseErrorKind::DuplicateTerm(Some('i')), 7));
+        assert_eq!(error("1 + 2"), (ParseErrorKind::DuplicateTerm(None), 2));
+        assert_eq!(error("2i + q"), (ParseErrorKind::UnexpectedCharacter('q'), 5));
+        assert_eq!(error("1.2.3"), (ParseErrorKind::UnexpectedCharacter('.'), 3));
+        assert_eq!(error("(1, 2, 3, 4) extra"), (ParseErrorKind::UnexpectedCharacter('e'), 13));
+        let message = "1 + 2i - 3i".parse::<Quaternion>().unwrap_err().to_string();
+        assert_eq!(message, "duplicate 'i' term at position 7");
+    }
+}
@@ -0,0 +1,131 @@
+//! Closed-form point set registration with Horn's quaternion method.
+
+use num_traits::Float;
+use crate::vector::{add, dot, scale, sub};
+use crate::wahba::{davenport, Observation};
+use crate::{Quaternion, QuaternionError, UnitQuaternion};
+
+/// Transform `target ~ scale * rotation * source + translation` with the weighted
+/// root mean square of its residuals.
+#[derive(PartialEq, Copy, Clone, Debug)]
+pub struct Alignment<T = f64> {
+    pub rotation: Quaternion<T>,
+    pub translation: [T; 3],
+    pub scale: T,
+    pub rms: T,
+}
+
+impl<T: Float> Alignment<T> {
+    pub fn transform_point(&self, alpha: [T; 3]) -> [T; 3] {
+        let rotated = UnitQuaternion::new_unchecked(self.rotation).rotate_vector(alpha);
+        add(scale(rotated, self.scale), self.translation)
+    }
+}
+
+/// Best rigid transform taking `source` onto `target` in the weighted least squares
+/// sense; all weights are one if `weights` is `None`. Fails if the points are
+/// collinear.
+///
+/// # Panics
+///
+/// Panics if `source`, `target` and `weights` differ in length.
+pub fn horn_align<T: Float>(source: &[[T; 3]], target: &[[T; 3]], weights: Option<&[T]>) -> Result<Alignment<T>, QuaternionError> {
+    align(source, target, weights, false)
+}
+
+/// Like `horn_align`, but also estimates a uniform scale, using Horn's symmetric
+/// estimate `sqrt(sum(w |target - c_t|^2) / sum(w |source - c_s|^2))`.
+pub fn horn_align_scaled<T: Float>(source: &[[T; 3]], target: &[[T; 3]], weights: Option<&[T]>) -> Result<Alignment<T>, QuaternionError> {
+    align(source, target, weights, true)
+}
+
+fn align<T: Float>(source: &[[T; 3]], target: &[[T; 3]], weights: Option<&[T]>, with_scale: bool) -> Result<Alignment<T>, QuaternionError> {
+    assert_eq!(source.len(), target.len(), "source and target must have the same length");
+    let ones = vec![T::one(); source.len()];
+    let weights = weights.unwrap_or(&ones);
+    assert_eq!(source.len(), weights.len(), "one weight per point is required");
+    let total = weights.iter().fold(T::zero(), |sum, w| sum + *w);
+    if total <= T::zero() {
+        return Err(QuaternionError::Degenerate);
+    }
+    let centroid = |points: &[[T; 3]]| {
+        let sum = points.iter().zip(weights).fold([T::zero(); 3], |sum, (p, w)| add(sum, scale(*p, *w)));
+        scale(sum, T::one() / total)
+    };
+    let (source_centroid, target_centroid) = (centroid(source), centroid(target));
+    let observations: Vec<Observation<T>> = source
+        .iter()
+        .zip(target)
+        .zip(weights)
+        .map(|((s, t), w)| Observation::new(sub(*s, source_centroid), sub(*t, target_centroid), *w))
+        .collect();
+    let rotation = davenport(&observations)?;
+    let s = if with_scale {
+        let spread = |f: fn(&Observation<T>) -> [T; 3]| {
+            observations.iter().fold(T::zero(), |sum, o| sum + o.weight * dot(f(o), f(o)))
+        };
+        (spread(|o| o.reference) / spread(|o| o.body)).sqrt()
+    } else {
+        T::one()
+    };
+    let rotated = UnitQuaternion::new_unchecked(rotation).rotate_vector(source_centroid);
+    let mut alignment = Alignment { rotation, translation: sub(target_centroid, scale(rotated, s)), scale: s, rms: T::zero() };
+    let squared = source.iter().zip(target).zip(weights).fold(T::zero(), |sum, ((p, t), w)| {
+        let r = sub(*t, alignment.transform_point(*p));
+        sum + *w * dot(r, r)
+    });
+    alignment.rms = (squared / total).sqrt();
+    Ok(alignment)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const SOURCE: [[f64; 3]; 5] = [[0.0, 0.0, 0.0], [1.0, 0.0, 0.5], [0.0, 2.0, -1.0], [-1.0, 1.0, 3.0], [2.0, -1.0, 1.0]];
+
+    fn transformed(rotation: Quaternion, translation: [f64; 3], s: f64) -> Vec<[f64; 3]> {
+        let r = UnitQuaternion::new(rotation);
+        SOURCE.iter().map(|p| add(scale(r.rotate_vector(*p), s), translation)).collect()
+    }
+
+    #[test]
+    fn test_rigid() {
+        let rotation = Quaternion::from_axis_angle([0.3, -1.0, 2.0], 2.4);
+        let target = transformed(rotation, [1.0, -2.0, 0.5], 1.0);
+        let alignment = horn_align(&SOURCE, &target, None).unwrap();
+        assert!(alignment.rotation.rotation_eq(&rotation, 1e-12));
+        assert!((0..3).all(|n| (alignment.translation[n] - [1.0, -2.0, 0.5][n]).abs() < 1e-12));
+        assert_eq!(alignment.scale, 1.0);
+        assert!(alignment.rms < 1e-12);
+    }
+    #[test]
+    fn test_scaled() {
+        let rotation = Quaternion::from_axis_angle([1.0, 1.0, 0.0], -0.7);
+        let target = transformed(rotation, [0.0, 4.0, -3.0], 2.5);
+        let alignment = horn_align_scaled(&SOURCE, &target, Some(&[1.0, 2.0, 1.0, 0.5, 3.0])).unwrap();
+        assert!(alignment.rotation.rotation_eq(&rotation, 1e-12));
+        assert!((alignment.scale - 2.5).abs() < 1e-12);
+        assert!(alignment.rms < 1e-12);
+        assert!(horn_align(&SOURCE, &target, None).unwrap().rms > 1.0);
+    }
+    #[test]
+    fn test_residual() {
+        let mut target = SOURCE.to_vec();
+        target[0][2] += 0.1;
+        let alignment = horn_align(&SOURCE, &target, None).unwrap();
+        assert!(alignment.rms > 0.0 && alignment.rms < 0.1);
+        let collinear = [[0.0, 0.0, 0.0], [1.0, 1.0, 1.0], [2.0, 2.0, 2.0]];
+        assert_eq!(horn_align(&collinear, &collinear, None), Err(QuaternionError::Degenerate));
+    }
+    #[test]
+    fn test_point_at_centroid() {
+        let star = [[0.0, 0.0, 0.0], [1.0, 0.0, 0.0], [-1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, -1.0, 0.0]];
+        let alignment = horn_align(&star, &star, None).unwrap();
+        assert!(alignment.rotation.rotation_eq(&Quaternion::identity(), 1e-12));
+        assert!(alignment.rms < 1e-12);
+        let rotation = Quaternion::from_axis_angle([0.0, 0.0, 1.0], 0.8);
+        let target: Vec<[f64; 3]> = star.iter().map(|p| UnitQuaternion::new(rotation).rotate_vector(*p)).collect();
+        assert!(horn_align(&star, &target, None).unwrap().rotation.rotation_eq(&rotation, 1e-12));
+    }
+}
@@ -0,0 +1,121 @@
+//! Serde support, enabled by the `serde` feature.
+//!
+//! `Quaternion` serializes as the scalar-first array `[w, x, y, z]`. Fields using
+//! another layout select it with `#[serde(with = "rust_quaternion::serialization::scalar_last")]`
+//! or `named`; `scalar_first` spells out the default.
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use crate::Quaternion;
+
+impl<T: Serialize + Copy> Serialize for Quaternion<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        scalar_first::serialize(self, serializer)
+    }
+}
+
+impl<'de, T: Deserialize<'de>> Deserialize<'de> for Quaternion<T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Quaternion<T>, D::Error> {
+        scalar_first::deserialize(deserializer)
+    }
+}
+
+/// The array `[w, x, y, z]`.
+pub mod scalar_first {
+    use super::*;
+
+    pub fn serialize<T: Serialize + Copy, S: Serializer>(alpha: &Quaternion<T>, serializer: S) -> Result<S::Ok, S::Error> {
+        [alpha.i, alpha.j, alpha.k, alpha.l].serialize(serializer)
+    }
+    pub fn deserialize<'de, T: Deserialize<'de>, D: Deserializer<'de>>(deserializer: D) -> Result<Quaternion<T>, D::Error> {
+        let [w, x, y, z] = <[T; 4]>::deserialize(deserializer)?;
+        Ok(Quaternion { i: w, j: x, k: y, l: z })
+    }
+}
+
+/// The array `[x, y, z, w]`, as used by ROS, Eigen's storage order and Unity.
+pub mod scalar_last {
+    use super::*;
+
+    pub fn serialize<T: Serialize + Copy, S: Serializer>(alpha: &Quaternion<T>, serializer: S) -> Result<S::Ok, S::Error> {
+        [alpha.j, alpha.k, alpha.l, alpha.i].serialize(serializer)
+    }
+    pub fn deserialize<'de, T: Deserialize<'de>, D: Deserializer<'de>>(deserializer: D) -> Result<Quaternion<T>, D::Error> {
+        let [x, y, z, w] = <[T; 4]>::deserialize(deserializer)?;
+        Ok(Quaternion { i: w, j: x, k: y, l: z })
+    }
+}
+
+/// The struct `{ w, x, y, z }`.
+pub mod named {
+    use super::*;
+
+    #[derive(Serialize, Deserialize)]
+    #[serde(rename = "Quaternion")]
+    struct Named<T> {
+        w: T,
+        x: T,
+        y: T,
+        z: T,
+    }
+
+    pub fn serialize<T: Serialize + Copy, S: Serializer>(alpha: &Quaternion<T>, serializer: S) -> Result<S::Ok, S::Error> {
+        Named { w: alpha.i, x: alpha.j, y: alpha.k, z: alpha.l }.serialize(serializer)
+    }
+    pub fn deserialize<'de, T: Deserialize<'de>, D: Deserializer<'de>>(deserializer: D) -> Result<Quaternion<T>, D::Error> {
+        let Named { w, x, y, z } = Named::deserialize(deserializer)?;
+        Ok(Quaternion { i: w, j: x, k: y, l: z })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use serde::{Deserialize, Serialize};
+    use crate::{Quaternion, Quaternion32};
+
+    #[derive(Serialize, Deserialize, PartialEq, Debug)]
+    struct Pose {
+        default: Quaternion,
+        #[serde(with = "super::scalar_first")]
+        first: Quaternion,
+        #[serde(with = "super::scalar_last")]
+        last: Quaternion,
+        #[serde(with = "super::named")]
+        named: Quaternion,
+    }
+
+    fn pose() -> Pose {
+        let q = Quaternion::new(0.5, -0.5, 0.25, 1.5);
+        Pose { default: q, first: q, last: q, named: q }
+    }
+
+    #[test]
+    fn test_json() {
+        let json = serde_json::to_string(&pose()).unwrap();
+        assert_eq!(
+            json,
+            r#"{"default":[0.5,-0.5,0.25,1.5],"first":[0.5,-0.5,0.25,1.5],"last":[-0.5,0.25,1.5,0.5],"named":{"w":0.5,"x":-0.5,"y":0.25,"z":1.5}}"#
+        );
+        assert_eq!(serde_json::from_str::<Pose>(&json).unwrap(), pose());
+        let q: Quaternion32 = serde_json::from_str("[1, 2, 3, 4]").unwrap();
+        assert_eq!(q, Quaternion::new(1.0, 2.0, 3.0, 4.0));
+        assert!(serde_json::from_str::<Quaternion>("[1, 2, 3]").is_err());
+        assert!(serde_json::from_str::<Quaternion>(r#"{"w": 1, "x": 2, "y": 3, "z": 4}"#).is_err());
+    }
+    #[test]
+    fn test_toml() {
+        let text = toml::to_string(&pose()).unwrap();
+        assert_eq!(toml::from_str::<Pose>(&text).unwrap(), pose());
+        let config = r#"
+            default = [0.5, -0.5, 0.25, 1.5]
+            first = [0.5, -0.5, 0.25, 1.5]
+            last = [-0.5, 0.25, 1.5, 0.5]
+
+            [named]
+            z = 1.5
+            w = 0.5
+            x = -0.5
+            y = 0.25
+        "#;
+        assert_eq!(toml::from_str::<Pose>(config).unwrap(), pose());
+    }
+}
@@ -0,0 +1,306 @@
+//! Rotation curves through sequences of keyframe quaternions.
+//!
+//! All splines are parameterized uniformly: segment `n` covers `u` in `[n, n + 1]`.
+//! Angular velocities are given in the body frame, `omega = 2 * q.conj() * dq/du`,
+//! in radians per unit of `u`.
+
+use num_traits::Float;
+use crate::vector::{scale, sub};
+use crate::{cast, Quaternion};
+
+pub trait RotationSpline<T: Float> {
+    /// Largest valid parameter; `evaluate` accepts `u` in `[0, end()]`.
+    fn end(&self) -> T;
+    fn evaluate(&self, u: T) -> Quaternion<T>;
+    /// Body-frame angular velocity at `u`.
+    ///
+    /// The default is a finite difference on the rotation group with step
+    /// `h = eps^(1/3)` that never leaves the unit segment containing `u`, where the
+    /// curve is smooth: central in the interior of a segment and the second-order
+    /// one-sided formula `(4 f(h) - f(2h)) / 2h` within `h` of a key. Keys use the
+    /// segment that starts there, or the last segment at `end()`. The error is about
+    /// `1e-9` relative everywhere, keys included.
+    fn angular_velocity(&self, u: T) -> [T; 3] {
+        let h = T::epsilon().cbrt();
+        let two: T = cast(2.0);
+        let four: T = cast(4.0);
+        let start = u.floor().min(self.end() - T::one()).max(T::zero());
+        let q = self.evaluate(u);
+        let log = |s: T| (q.conj() * self.evaluate(u + s)).to_rotation_vector();
+        let v = if u - h < start {
+            sub(scale(log(h), four), log(two * h))
+        } else if u + h > start + T::one() {
+            sub(log(-two * h), scale(log(-h), four))
+        } else {
+            (self.evaluate(u - h).conj() * self.evaluate(u + h)).to_rotation_vector()
+        };
+        scale(v, T::one() / (two * h))
+    }
+}
+
+/// Normalizes `keys` and flips their signs so that neighbours lie in the same hemisphere.
+fn align<T: Float>(keys: &[Quaternion<T>]) -> Vec<Quaternion<T>> {
+    let mut aligned: Vec<Quaternion<T>> = Vec::with_capacity(keys.len());
+    for key in keys {
+        let q = key.unit();
+        match aligned.last() {
+            Some(p) if p.dot(&q) < T::zero() => aligned.push(-q),
+            _ => aligned.push(q),
+        }
+    }
+    aligned
+}
+
+fn segment<T: Float>(u: T, count: usize) -> (usize, T) {
+    let u = u.max(T::zero()).min(cast(count as f64));
+    let n = u.floor().to_usize().unwrap().min(count - 1);
+    (n, u - cast(n as f64))
+}
+
+/// `alpha * (alpha.conj() * beta)^t` without hemisphere correction.
+fn slerp<T: Float>(alpha: Quaternion<T>, beta: Quaternion<T>, t: T) -> Quaternion<T> {
+    (alpha * ((alpha.conj() * beta).ln() * t).exp()).unit()
+}
+
+/// Logarithm of the relative rotation `alpha.conj() * beta`.
+fn log_delta<T: Float>(alpha: Quaternion<T>, beta: Quaternion<T>) -> Quaternion<T> {
+    (alpha.conj() * beta).ln()
+}
+
+/// Shoemake's spherical quadrangle interpolation, C1 continuous through the keys.
+#[derive(Clone, Debug)]
+pub struct Squad<T = f64> {
+    keys: Vec<Quaternion<T>>,
+    controls: Vec<Quaternion<T>>,
+}
+
+impl<T: Float> Squad<T> {
+    /// # Panics
+    ///
+    /// Panics if fewer than two keys are given.
+    pub fn new(keys: &[Quaternion<T>]) -> Squad<T> {
+        assert!(keys.len() >= 2, "Squad needs at least two keys");
+        let keys = align(keys);
+        let last = keys.len() - 1;
+        let controls = (0..keys.len())
+            .map(|n| {
+                if n == 0 || n == last {
+                    return keys[n];
+                }
+                let d = log_delta(keys[n], keys[n + 1]) + log_delta(keys[n], keys[n - 1]);
+                (keys[n] * (d * cast::<T>(-0.25)).exp()).unit()
+            })
+            .collect();
+        Self { keys, controls }
+    }
+}
+
+impl<T: Float> RotationSpline<T> for Squad<T> {
+    fn end(&self) -> T {
+        cast((self.keys.len() - 1) as f64)
+    }
+    fn evaluate(&self, u: T) -> Quaternion<T> {
+        let (n, t) = segment(u, self.keys.len() - 1);
+        let outer = slerp(self.keys[n], self.keys[n + 1], t);
+        let inner = slerp(self.controls[n], self.controls[n + 1], t);
+        slerp(outer, inner, cast::<T>(2.0) * t * (T::one() - t))
+    }
+}
+
+/// Catmull-Rom spline on the rotation group, evaluated as a spherical Bezier curve.
+///
+/// The tangent at each key is the mean of the logarithms of the adjacent relative
+/// rotations; the end keys use one-sided tangents.
+#[derive(Clone, Debug)]
+pub struct CatmullRom<T = f64> {
+    keys: Vec<Quaternion<T>>,
+    tangents: Vec<Quaternion<T>>,
+}
+
+impl<T: Float> CatmullRom<T> {
+    /// # Panics
+    ///
+    /// Panics if fewer than two keys are given.
+    pub fn new(keys: &[Quaternion<T>]) -> CatmullRom<T> {
+        assert!(keys.len() >= 2, "CatmullRom needs at least two keys");
+        let keys = align(keys);
+        let last = keys.len() - 1;
+        let tangents = (0..keys.len())
+            .map(|n| {
+                let previous = keys[n.saturating_sub(1)];
+                let next = keys[(n + 1).min(last)];
+                let d = log_delta(previous, keys[n]) + log_delta(keys[n], next);
+                if n == 0 || n == last { d } else { d * cast::<T>(0.5) }
+            })
+            .collect();
+        Self { keys, tangents }
+    }
+}
+
+impl<T: Float> RotationSpline<T> for CatmullRom<T> {
+    fn end(&self) -> T {
+        cast((self.keys.len() - 1) as f64)
+    }
+    fn evaluate(&self, u: T) -> Quaternion<T> {
+        let (n, t) = segment(u, self.keys.len() - 1);
+        let third: T = cast(1.0 / 3.0);
+        let p0 = self.keys[n];
+        let p3 = self.keys[n + 1];
+        let p1 = p0 * (self.tangents[n] * third).exp();
+        let p2 = p3 * (self.tangents[n + 1] * -third).exp();
+        let a = slerp(p0, p1, t);
+        let b = slerp(p1, p2, t);
+        let c = slerp(p2, p3, t);
+        slerp(slerp(a, b, t), slerp(b, c, t), t)
+    }
+}
+
+/// Kim-Kim-Shin cumulative cubic B-spline, C2 continuous but approximating: the
+/// curve does not pass through the keys. Segment `n` is shaped by keys `n` to `n + 3`.
+#[derive(Clone, Debug)]
+pub struct CumulativeBSpline<T = f64> {
+    keys: Vec<Quaternion<T>>,
+    deltas: Vec<Quaternion<T>>,
+}
+
+impl<T: Float> CumulativeBSpline<T> {
+    /// # Panics
+    ///
+    /// Panics if fewer than four keys are given.
+    pub fn new(keys: &[Quaternion<T>]) -> CumulativeBSpline<T> {
+        assert!(keys.len() >= 4, "CumulativeBSpline needs at least four keys");
+        let keys = align(keys);
+        let deltas = keys.windows(2).map(|w| log_delta(w[0], w[1])).collect();
+        Self { keys, deltas }
+    }
+    fn basis(t: T) -> ([T; 3], [T; 3]) {
+        let sixth: T = cast(1.0 / 6.0);
+        let (t2, t3) = (t * t, t * t * t);
+        let three: T = cast(3.0);
+        let b = [
+            (cast::<T>(5.0) + three * t - three * t2 + t3) * sixth,
+            (T::one() + three * t + three * t2 - cast::<T>(2.0) * t3) * sixth,
+            t3 * sixth,
+        ];
+        let db = [
+            (three - cast::<T>(6.0) * t + three * t2) * sixth,
+            (three + cast::<T>(6.0) * t - cast::<T>(6.0) * t2) * sixth,
+            three * t2 * sixth,
+        ];
+        (b, db)
+    }
+}
+
+impl<T: Float> RotationSpline<T> for CumulativeBSpline<T> {
+    fn end(&self) -> T {
+        cast((self.keys.len() - 3) as f64)
+    }
+    fn evaluate(&self, u: T) -> Quaternion<T> {
+        let (n, t) = segment(u, self.keys.len() - 3);
+        let (b, _) = CumulativeBSpline::basis(t);
+        (0..3).fold(self.keys[n], |q, j| q * (self.deltas[n + j] * b[j]).exp()).unit()
+    }
+    /// Analytic derivative of the cumulative basis.
+    fn angular_velocity(&self, u: T) -> [T; 3] {
+        let (n, t) = segment(u, self.keys.len() - 3);
+        let (b, db) = CumulativeBSpline::basis(t);
+        let factors: Vec<Quaternion<T>> = (0..3).map(|j| (self.deltas[n + j] * b[j]).exp()).collect();
+        let zero = Quaternion::zero();
+        let mut q = self.keys[n];
+        let mut dq = zero;
+        for j in 0..3 {
+            dq = dq * factors[j] + q * (self.deltas[n + j] * db[j]) * factors[j];
+            q *= factors[j];
+        }
+        (q.conj() * dq * cast::<T>(2.0)).vector()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{CatmullRom, CumulativeBSpline, RotationSpline, Squad};
+    use crate::Quaternion;
+
+    fn keys() -> Vec<Quaternion> {
+        vec![
+            Quaternion::from_axis_angle([0.0, 1.0, 0.0], 0.1),
+            Quaternion::from_axis_angle([1.0, 2.0, 0.0], 0.8),
+            -Quaternion::from_axis_angle([0.0, -1.0, 1.0], 1.7),
+            Quaternion::from_axis_angle([3.0, 0.0, 1.0], 0.4),
+            Quaternion::from_axis_angle([0.0, 0.0, 1.0], 2.9),
+        ]
+    }
+
+    fn assert_same_rotation(a: Quaternion, b: Quaternion, eps: f64) {
+        assert!((a.dot(&b).abs() - 1.0).abs() < eps, "{} != {}", a, b);
+    }
+
+    fn assert_close(a: [f64; 3], b: [f64; 3], eps: f64) {
+        for n in 0..3 {
+            assert!((a[n] - b[n]).abs() < eps, "{:?} != {:?}", a, b);
+        }
+    }
+
+    fn about_z(keys: usize, step: f64) -> Vec<Quaternion> {
+        (0..keys).map(|n| Quaternion::from_axis_angle([0.0, 0.0, 1.0], step * n as f64)).collect()
+    }
+
+    #[test]
+    fn test_interpolates_keys() {
+        let keys = keys();
+        let squad = Squad::new(&keys);
+        let catmull_rom = CatmullRom::new(&keys);
+        for (n, key) in keys.iter().enumerate() {
+            assert_same_rotation(squad.evaluate(n as f64), *key, 1e-12);
+            assert_same_rotation(catmull_rom.evaluate(n as f64), *key, 1e-12);
+        }
+    }
+    #[test]
+    fn test_c1_continuity() {
+        let keys = keys();
+        let splines: [&dyn RotationSpline<f64>; 3] =
+            [&Squad::new(&keys), &CatmullRom::new(&keys), &CumulativeBSpline::new(&keys)];
+        for spline in splines {
+            let at = spline.angular_velocity(1.0);
+            assert_close(spline.angular_velocity(1.0 - 1e-8), at, 1e-6);
+            assert_close(spline.angular_velocity(1.0 + 1e-8), at, 1e-6);
+        }
+    }
+    #[test]
+    fn test_uniform_rotation() {
+        let keys = about_z(6, 0.3);
+        assert_close(Squad::new(&keys).angular_velocity(2.4), [0.0, 0.0, 0.3], 1e-8);
+        assert_close(CatmullRom::new(&keys).angular_velocity(1.7), [0.0, 0.0, 0.3], 1e-8);
+        let bspline = CumulativeBSpline::new(&keys);
+        assert_close(bspline.angular_velocity(1.3), [0.0, 0.0, 0.3], 1e-12);
+        assert_same_rotation(bspline.evaluate(0.0), keys[1], 1e-12);
+    }
+    #[test]
+    fn test_key_velocity() {
+        let keys = keys();
+        let last = keys.len() - 1;
+        let delta = |n: usize| (keys[n].conj() * keys[n + 1]).to_rotation_vector();
+        let splines: [&dyn RotationSpline<f64>; 2] = [&Squad::new(&keys), &CatmullRom::new(&keys)];
+        for spline in splines {
+            assert_close(spline.angular_velocity(0.0), delta(0), 1e-8);
+            assert_close(spline.angular_velocity(last as f64), delta(last - 1), 1e-8);
+            for n in 1..last {
+                let (a, b) = (delta(n - 1), delta(n));
+                let expected = [(a[0] + b[0]) / 2.0, (a[1] + b[1]) / 2.0, (a[2] + b[2]) / 2.0];
+                assert_close(spline.angular_velocity(n as f64), expected, 1e-8);
+            }
+        }
+        let squad = Squad::new(&keys[..2]);
+        assert_close(squad.angular_velocity(1.0), delta(0), 1e-8);
+    }
+    #[test]
+    fn test_bspline_derivative() {
+        let bspline = CumulativeBSpline::new(&keys());
+        let h = 1e-6;
+        for u in [0.2, 0.9, 1.5] {
+            let v = (bspline.evaluate(u - h).conj() * bspline.evaluate(u + h)).to_rotation_vector();
+            let numeric = [v[0] / (2.0 * h), v[1] / (2.0 * h), v[2] / (2.0 * h)];
+            assert_close(bspline.angular_velocity(u), numeric, 1e-6);
+        }
+    }
+}
@@ -0,0 +1,126 @@
+use num_traits::Float;
+use crate::vector::{cross, dot, norm, scale, sub};
+use crate::{cast, lie, wrap_angle, Quaternion, UnitQuaternion};
+
+fn normalized<T: Float>(alpha: [T; 3]) -> [T; 3] {
+    scale(alpha, T::one() / norm(alpha))
+}
+
+impl<T: Float> UnitQuaternion<T> {
+    /// Splits the rotation into `(swing, twist)` with `self = swing * twist`, where
+    /// `twist` rotates about `axis` and `swing` rotates about an axis perpendicular
+    /// to it. When `self` is a half-turn perpendicular to `axis` the twist is identity.
+    pub fn swing_twist(&self, axis: [T; 3]) -> (UnitQuaternion<T>, UnitQuaternion<T>) {
+        let q = self.quaternion();
+        let axis = normalized(axis);
+        let projection = scale(axis, dot(q.vector(), axis));
+        let twist = Quaternion::new(q.i, projection[0], projection[1], projection[2]);
+        if twist.abs() < T::epsilon() {
+            return (*self, UnitQuaternion::identity());
+        }
+        let twist = UnitQuaternion::new(twist);
+        (UnitQuaternion::new(q * twist.quaternion().conj()), twist)
+    }
+    /// Signed twist angle about `axis` in `(-pi, pi]`.
+    pub fn twist_angle(&self, axis: [T; 3]) -> T {
+        let twist = self.swing_twist(axis).1.quaternion();
+        wrap_angle(cast::<T>(2.0) * dot(twist.vector(), normalized(axis)).atan2(twist.i))
+    }
+    /// Limits the twist about `axis` to `[min_angle, max_angle]`, keeping the swing.
+    pub fn clamp_twist(&self, axis: [T; 3], min_angle: T, max_angle: T) -> UnitQuaternion<T> {
+        let swing = self.swing_twist(axis).0;
+        let angle = self.twist_angle(axis).max(min_angle).min(max_angle);
+        swing * UnitQuaternion::new(Quaternion::from_axis_angle(axis, angle))
+    }
+    /// Limits the swing away from `axis` to a circular cone of half-angle `max_angle`,
+    /// keeping the twist.
+    pub fn clamp_swing(&self, axis: [T; 3], max_angle: T) -> UnitQuaternion<T> {
+        let (swing, twist) = self.swing_twist(axis);
+        let phi = lie::log(swing);
+        let angle = norm(phi);
+        if angle <= max_angle {
+            return *self;
+        }
+        lie::exp(scale(phi, max_angle / angle)) * twist
+    }
+    /// Limits the swing away from `axis` to an elliptical cone, keeping the twist.
+    /// `limit_reference` bounds the swing about `reference` (projected perpendicular
+    /// to `axis`) and `limit_orthogonal` the swing about `axis x reference`. A swing
+    /// outside the cone is scaled back onto its boundary, keeping its direction.
+    pub fn clamp_swing_elliptical(&self, axis: [T; 3], reference: [T; 3], limit_reference: T, limit_orthogonal: T) -> UnitQuaternion<T> {
+        let (swing, twist) = self.swing_twist(axis);
+        let axis = normalized(axis);
+        let u = normalized(sub(reference, scale(axis, dot(reference, axis))));
+        let v = cross(axis, u);
+        let phi = lie::log(swing);
+        let a = dot(phi, u) / limit_reference;
+        let b = dot(phi, v) / limit_orthogonal;
+        let radius = (a * a + b * b).sqrt();
+        if radius <= T::one() {
+            return *self;
+        }
+        lie::exp(scale(phi, T::one() / radius)) * twist
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::f64::consts::PI;
+    use crate::vector::{dot, norm};
+    use crate::{lie, Quaternion, UnitQuaternion};
+
+    fn rotation(axis: [f64; 3], angle: f64) -> UnitQuaternion {
+        UnitQuaternion::new(Quaternion::from_axis_angle(axis, angle))
+    }
+
+    #[test]
+    fn test_swing_twist() {
+        let axis = [0.0, 0.0, 2.0];
+        let q = rotation([1.0, 0.0, 0.0], 0.4) * rotation([0.0, 0.0, 1.0], 1.1);
+        let (swing, twist) = q.swing_twist(axis);
+        assert!((swing * twist).quaternion().approx_eq(&q.quaternion(), 1e-15));
+        assert!(twist.quaternion().rotation_eq(&rotation(axis, 1.1).quaternion(), 1e-15));
+        assert!(swing.quaternion().rotation_eq(&rotation([1.0, 0.0, 0.0], 0.4).quaternion(), 1e-15));
+        assert!(dot(swing.quaternion().vector(), axis).abs() < 1e-15);
+        assert!((q.twist_angle(axis) - 1.1).abs() < 1e-14);
+        assert!((rotation(axis, -2.5).twist_angle(axis) + 2.5).abs() < 1e-14);
+
+        let half_turn = rotation([0.0, 1.0, 0.0], PI);
+        let (swing, twist) = half_turn.swing_twist(axis);
+        assert_eq!(twist.quaternion(), Quaternion::new(1.0, 0.0, 0.0, 0.0));
+        assert_eq!(swing.quaternion(), half_turn.quaternion());
+    }
+    #[test]
+    fn test_clamp_twist() {
+        let axis = [0.0, 0.0, 1.0];
+        let q = rotation([0.0, 1.0, 0.0], 0.3) * rotation(axis, 1.2);
+        let clamped = q.clamp_twist(axis, -0.5, 0.5);
+        assert!((clamped.twist_angle(axis) - 0.5).abs() < 1e-14);
+        assert!(clamped.swing_twist(axis).0.quaternion().approx_eq(&q.swing_twist(axis).0.quaternion(), 1e-15));
+        assert!(q.clamp_twist(axis, -1.5, 1.5).quaternion().approx_eq(&q.quaternion(), 1e-15));
+    }
+    #[test]
+    fn test_clamp_swing() {
+        let axis = [0.0, 0.0, 1.0];
+        let q = rotation([1.0, 1.0, 0.0], 1.0) * rotation(axis, 0.7);
+        let clamped = q.clamp_swing(axis, 0.6);
+        let (swing, twist) = clamped.swing_twist(axis);
+        assert!((norm(lie::log(swing)) - 0.6).abs() < 1e-14);
+        assert!(twist.quaternion().approx_eq(&rotation(axis, 0.7).quaternion(), 1e-15));
+        assert!(q.clamp_swing(axis, 1.2).quaternion().approx_eq(&q.quaternion(), 1e-15));
+    }
+    #[test]
+    fn test_clamp_swing_elliptical() {
+        let axis = [0.0, 0.0, 1.0];
+        let reference = [1.0, 0.0, 0.5];
+        let q = rotation([1.0, 1.0, 0.0], 1.0) * rotation(axis, 0.7);
+        let clamped = q.clamp_swing_elliptical(axis, reference, 0.2, 0.4);
+        let phi = lie::log(clamped.swing_twist(axis).0);
+        assert!(((phi[0] / 0.2).powi(2) + (phi[1] / 0.4).powi(2) - 1.0).abs() < 1e-13);
+        assert!((phi[0] - phi[1]).abs() < 1e-14);
+        assert!((clamped.twist_angle(axis) - 0.7).abs() < 1e-14);
+        let circular = q.clamp_swing_elliptical(axis, reference, 0.6, 0.6);
+        assert!(circular.quaternion().approx_eq(&q.clamp_swing(axis, 0.6).quaternion(), 1e-15));
+        assert!(q.clamp_swing_elliptical(axis, reference, 1.2, 1.0).quaternion().approx_eq(&q.quaternion(), 1e-15));
+    }
+}
@@ -0,0 +1,111 @@
+use num_traits::Float;
+use crate::{cast, Quaternion};
+
+impl<T: Float> Quaternion<T> {
+    /// Exponential `e^w (cos|v| + v / |v| sin|v|)` of `w + v`.
+    pub fn exp(&self) -> Quaternion<T> {
+        let n = self.vector_abs();
+        let ew = self.i.exp();
+        // sin(n) / n, with its Taylor expansion near zero
+        let s = if n < T::epsilon().sqrt().sqrt() {
+            T::one() - n * n / cast(6.0)
+        } else {
+            n.sin() / n
+        };
+        Quaternion::new(ew * n.cos(), ew * s * self.j, ew * s * self.k, ew * s * self.l)
+    }
+    /// Principal logarithm `ln|q| + v / |v| atan2(|v|, w)`.
+    ///
+    /// For negative real quaternions the imaginary part is `pi` along `i`, and the
+    /// zero quaternion yields a real part of negative infinity.
+    pub fn ln(&self) -> Quaternion<T> {
+        let n = self.vector_abs();
+        let r = self.abs().ln();
+        if n == T::zero() {
+            let pi = if self.i < T::zero() { cast(std::f64::consts::PI) } else { T::zero() };
+            return Quaternion::new(r, pi, T::zero(), T::zero());
+        }
+        let s = n.atan2(self.i) / n;
+        Quaternion::new(r, s * self.j, s * self.k, s * self.l)
+    }
+    /// Real power `exp(alpha * ln(q))`.
+    pub fn powf(&self, alpha: T) -> Quaternion<T> {
+        (self.ln() * alpha).exp()
+    }
+    /// Quaternion power `exp(ln(q) * alpha)`, with `alpha` multiplied from the right.
+    pub fn powq(&self, alpha: Quaternion<T>) -> Quaternion<T> {
+        (self.ln() * alpha).exp()
+    }
+    /// Principal square root, with non-negative real part.
+    ///
+    /// Only the larger of `(|q| + w) / 2` and `(|q| - w) / 2` is taken by square
+    /// root; the other part follows from `v = 2 w' v'`, avoiding cancellation for
+    /// nearly real quaternions.
+    pub fn sqrt(&self) -> Quaternion<T> {
+        let n = self.vector_abs();
+        let a = self.abs();
+        let half: T = cast(0.5);
+        if self.i >= T::zero() {
+            let w = ((a + self.i) * half).sqrt();
+            if w == T::zero() {
+                return *self;
+            }
+            let s = half / w;
+            return Quaternion::new(w, s * self.j, s * self.k, s * self.l);
+        }
+        let v = ((a - self.i) * half).sqrt();
+        if n == T::zero() {
+            return Quaternion::new(T::zero(), v, T::zero(), T::zero());
+        }
+        let s = v / n;
+        Quaternion::new(n * half / v, s * self.j, s * self.k, s * self.l)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::Quaternion;
+
+    fn assert_close(a: Quaternion, b: Quaternion, eps: f64) {
+        assert!((a - b).abs() < eps, "{} != {}", a, b);
+    }
+
+    #[test]
+    fn test_exp_ln() {
+        let q = Quaternion::new(0.7, -1.2, 0.4, 2.1);
+        assert_close(q.ln().exp(), q, 1e-14);
+        assert_close(Quaternion::new(0.3, 0.5, -0.2, 0.1).exp().ln(), Quaternion::new(0.3, 0.5, -0.2, 0.1), 1e-14);
+        assert_close(Quaternion::new(1.0, 1e-12, 0.0, 0.0).exp(), Quaternion::new(1f64.exp(), 1f64.exp() * 1e-12, 0.0, 0.0), 1e-15);
+        let pi = std::f64::consts::PI;
+        assert_close(Quaternion::new(0.0, 0.0, pi, 0.0).exp(), Quaternion::new(-1.0, 0.0, 0.0, 0.0), 1e-15);
+        assert_close(Quaternion::new(-2.0, 0.0, 0.0, 0.0).ln(), Quaternion::new(2f64.ln(), pi, 0.0, 0.0), 1e-15);
+    }
+    #[test]
+    fn test_powers() {
+        let q = Quaternion::new(0.7, -1.2, 0.4, 2.1);
+        assert_close(q.powf(2.0), q * q, 1e-13);
+        assert_close(q.powf(3.0), q * q * q, 1e-12);
+        assert_close(q.sqrt() * q.sqrt(), q, 1e-14);
+        assert_close(q.sqrt(), q.powf(0.5), 1e-14);
+        assert_close(q.powq(Quaternion::new(2.0, 0.0, 0.0, 0.0)), q * q, 1e-13);
+        let r = Quaternion::new(-4.0, 0.0, 0.0, 0.0).sqrt();
+        assert_close(r, Quaternion::new(0.0, 2.0, 0.0, 0.0), 1e-15);
+        assert_close(r * r, Quaternion::new(-4.0, 0.0, 0.0, 0.0), 1e-14);
+        assert_eq!(Quaternion::new(0.0, 0.0, 0.0, 0.0).sqrt(), Quaternion::new(0.0, 0.0, 0.0, 0.0));
+        let q = Quaternion::new(-1.0, 0.3, -0.2, 0.5);
+        assert_close(q.sqrt() * q.sqrt(), q, 1e-14);
+    }
+    #[test]
+    fn test_sqrt_near_real() {
+        let r: Quaternion = Quaternion::new(1.0, 1e-10, 0.0, 0.0).sqrt();
+        assert_eq!(r.real(), 1.0);
+        assert!((r.vector()[0] - 5e-11).abs() < 1e-26);
+        let q: Quaternion = Quaternion::new(4.0, 1e-7, -2e-7, 0.0);
+        let r = q.sqrt();
+        assert!((r.vector()[0] - 2.5e-8).abs() < 1e-22 && (r.vector()[1] + 5e-8).abs() < 1e-22, "{:e}", r);
+        assert!(r.relative_eq(&q.powf(0.5), 0.0, 1e-12));
+        let r = Quaternion::from_axis_angle([1.0, 2.0, 2.0], 1e-6).sqrt();
+        let expected = Quaternion::from_axis_angle([1.0, 2.0, 2.0], 5e-7);
+        assert!(r.relative_eq(&expected, 0.0, 1e-12), "{:e} != {:e}", r, expected);
+    }
+}
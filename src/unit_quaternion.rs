@@ -0,0 +1,107 @@
+use std::ops::Mul;
+use std::fmt::{Display, Formatter, Result};
+use num_traits::Float;
+use crate::Quaternion;
+
+/// Quaternion of norm one, representing a rotation in three dimensions.
+///
+/// Rotations are active: `rotate_vector` maps `v` to `q * v * q.conj()`.
+#[derive(PartialEq, PartialOrd, Copy, Clone, Debug)]
+pub struct UnitQuaternion<T = f64> {
+    q: Quaternion<T>,
+}
+
+impl<T: Float> UnitQuaternion<T> {
+    /// Normalizes `alpha`. The zero quaternion yields NaN components.
+    pub fn new(alpha: Quaternion<T>) -> UnitQuaternion<T> {
+        Self { q: alpha.unit() }
+    }
+    /// Wraps `alpha` without normalizing; the caller guarantees `alpha.abs() == 1`.
+    pub fn new_unchecked(alpha: Quaternion<T>) -> UnitQuaternion<T> {
+        Self { q: alpha }
+    }
+    pub fn identity() -> UnitQuaternion<T> {
+        Self { q: Quaternion::identity() }
+    }
+    pub fn quaternion(&self) -> Quaternion<T> {
+        self.q
+    }
+    pub fn conj(&self) -> UnitQuaternion<T> {
+        Self { q: self.q.conj() }
+    }
+    pub fn inverse(&self) -> UnitQuaternion<T> {
+        self.conj()
+    }
+    pub fn renormalize(&mut self) {
+        self.q = self.q.unit();
+    }
+    pub fn rotate_vector(&self, alpha: [T; 3]) -> [T; 3] {
+        (self.q * Quaternion::from_vector(alpha) * self.q.conj()).vector()
+    }
+    pub fn inverse_rotate_vector(&self, alpha: [T; 3]) -> [T; 3] {
+        (self.q.conj() * Quaternion::from_vector(alpha) * self.q).vector()
+    }
+    pub fn rotate_vectors(&self, points: &[[T; 3]]) -> Vec<[T; 3]> {
+        points.iter().map(|p| self.rotate_vector(*p)).collect()
+    }
+    pub fn rotate_vectors_in_place(&self, points: &mut [[T; 3]]) {
+        for p in points.iter_mut() {
+            *p = self.rotate_vector(*p);
+        }
+    }
+}
+
+impl<T: Float> Mul for UnitQuaternion<T> {
+    type Output = UnitQuaternion<T>;
+    fn mul(self, alpha: UnitQuaternion<T>) -> UnitQuaternion<T> {
+        UnitQuaternion::new(self.q * alpha.q)
+    }
+}
+
+impl<T> From<UnitQuaternion<T>> for Quaternion<T> {
+    fn from(alpha: UnitQuaternion<T>) -> Quaternion<T> {
+        alpha.q
+    }
+}
+
+impl<T: Float + Display> Display for UnitQuaternion<T> {
+    fn fmt(&self, f: &mut Formatter) -> Result {
+        self.q.fmt(f)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::UnitQuaternion;
+    use crate::Quaternion;
+
+    fn assert_close(a: [f64; 3], b: [f64; 3]) {
+        for n in 0..3 {
+            assert!((a[n] - b[n]).abs() < 1e-12, "{:?} != {:?}", a, b);
+        }
+    }
+
+    #[test]
+    fn test_rotate_vector() {
+        let h = 0.5f64.sqrt();
+        let q = UnitQuaternion::new(Quaternion::new(h, 0.0, 0.0, h));
+        assert_close(q.rotate_vector([1.0, 0.0, 0.0]), [0.0, 1.0, 0.0]);
+        assert_close(q.inverse_rotate_vector([0.0, 1.0, 0.0]), [1.0, 0.0, 0.0]);
+        let mut points = [[1.0, 0.0, 0.0], [0.0, 0.0, 2.0]];
+        assert_close(q.rotate_vectors(&points)[1], [0.0, 0.0, 2.0]);
+        q.rotate_vectors_in_place(&mut points);
+        assert_close(points[0], [0.0, 1.0, 0.0]);
+    }
+    #[test]
+    fn test_composition() {
+        let q = UnitQuaternion::new(Quaternion::new(2.0, 1.0, -3.0, 0.5));
+        let r = UnitQuaternion::new(Quaternion::new(-1.0, 4.0, 0.2, 1.0));
+        let v = [0.3, -1.2, 2.5];
+        assert_close((q * r).rotate_vector(v), q.rotate_vector(r.rotate_vector(v)));
+        let mut p = UnitQuaternion::identity();
+        for _ in 0..10000 {
+            p = p * q;
+        }
+        assert!((p.quaternion().abs() - 1.0).abs() < 1e-12);
+    }
+}
@@ -0,0 +1,153 @@
+use num_traits::Float;
+
+pub(crate) fn dot<T: Float>(alpha: [T; 3], beta: [T; 3]) -> T {
+    alpha[0] * beta[0] + alpha[1] * beta[1] + alpha[2] * beta[2]
+}
+
+pub(crate) fn norm<T: Float>(alpha: [T; 3]) -> T {
+    dot(alpha, alpha).sqrt()
+}
+
+pub(crate) fn cross<T: Float>(alpha: [T; 3], beta: [T; 3]) -> [T; 3] {
+    [
+        alpha[1] * beta[2] - alpha[2] * beta[1],
+        alpha[2] * beta[0] - alpha[0] * beta[2],
+        alpha[0] * beta[1] - alpha[1] * beta[0],
+    ]
+}
+
+pub(crate) fn add<T: Float>(alpha: [T; 3], beta: [T; 3]) -> [T; 3] {
+    [alpha[0] + beta[0], alpha[1] + beta[1], alpha[2] + beta[2]]
+}
+
+pub(crate) fn sub<T: Float>(alpha: [T; 3], beta: [T; 3]) -> [T; 3] {
+    [alpha[0] - beta[0], alpha[1] - beta[1], alpha[2] - beta[2]]
+}
+
+pub(crate) fn scale<T: Float>(alpha: [T; 3], beta: T) -> [T; 3] {
+    [alpha[0] * beta, alpha[1] * beta, alpha[2] * beta]
+}
+
+pub(crate) fn hat<T: Float>(alpha: [T; 3]) -> [[T; 3]; 3] {
+    let zero = T::zero();
+    [
+        [zero, -alpha[2], alpha[1]],
+        [alpha[2], zero, -alpha[0]],
+        [-alpha[1], alpha[0], zero],
+    ]
+}
+
+pub(crate) fn identity<T: Float>() -> [[T; 3]; 3] {
+    let (zero, one) = (T::zero(), T::one());
+    [[one, zero, zero], [zero, one, zero], [zero, zero, one]]
+}
+
+pub(crate) fn mat_mul<T: Float, const R: usize, const K: usize, const C: usize>(
+    alpha: [[T; K]; R],
+    beta: [[T; C]; K],
+) -> [[T; C]; R] {
+    let mut m = [[T::zero(); C]; R];
+    for row in 0..R {
+        for col in 0..C {
+            m[row][col] = (0..K).fold(T::zero(), |sum, n| sum + alpha[row][n] * beta[n][col]);
+        }
+    }
+    m
+}
+
+pub(crate) fn mat_vec<T: Float, const R: usize, const C: usize>(alpha: [[T; C]; R], beta: [T; C]) -> [T; R] {
+    alpha.map(|row| (0..C).fold(T::zero(), |sum, n| sum + row[n] * beta[n]))
+}
+
+pub(crate) fn transpose<T: Float, const R: usize, const C: usize>(alpha: [[T; C]; R]) -> [[T; R]; C] {
+    let mut m = [[T::zero(); R]; C];
+    for row in 0..R {
+        for col in 0..C {
+            m[col][row] = alpha[row][col];
+        }
+    }
+    m
+}
+
+pub(crate) fn mat_add<T: Float, const R: usize, const C: usize>(alpha: [[T; C]; R], beta: [[T; C]; R]) -> [[T; C]; R] {
+    let mut m = alpha;
+    for row in 0..R {
+        for col in 0..C {
+            m[row][col] = m[row][col] + beta[row][col];
+        }
+    }
+    m
+}
+
+pub(crate) fn mat_scale<T: Float, const R: usize, const C: usize>(alpha: [[T; C]; R], beta: T) -> [[T; C]; R] {
+    alpha.map(|row| row.map(|x| x * beta))
+}
+
+pub(crate) fn adjugate3<T: Float>(alpha: [[T; 3]; 3]) -> [[T; 3]; 3] {
+    transpose([
+        cross(alpha[1], alpha[2]),
+        cross(alpha[2], alpha[0]),
+        cross(alpha[0], alpha[1]),
+    ])
+}
+
+pub(crate) fn determinant3<T: Float>(alpha: [[T; 3]; 3]) -> T {
+    dot(alpha[0], cross(alpha[1], alpha[2]))
+}
+
+/// Inverse of a 3x3 matrix by its adjugate; `None` if singular.
+pub(crate) fn inverse3<T: Float>(alpha: [[T; 3]; 3]) -> Option<[[T; 3]; 3]> {
+    let determinant = determinant3(alpha);
+    if determinant == T::zero() {
+        return None;
+    }
+    Some(mat_scale(adjugate3(alpha), T::one() / determinant))
+}
+
+/// Eigenvalues and eigenvectors (as columns) of a symmetric matrix by cyclic Jacobi
+/// rotations.
+pub(crate) fn symmetric_eigen<T: Float, const N: usize>(alpha: [[T; N]; N]) -> ([T; N], [[T; N]; N]) {
+    let mut a = alpha;
+    let mut v = [[T::zero(); N]; N];
+    for (n, row) in v.iter_mut().enumerate() {
+        row[n] = T::one();
+    }
+    let scale = a.iter().flatten().fold(T::zero(), |m, x| m.max(x.abs()));
+    for _ in 0..64 {
+        let off = (0..N).flat_map(|p| (p + 1..N).map(move |q| (p, q))).fold(T::zero(), |m, (p, q)| m.max(a[p][q].abs()));
+        if off <= T::epsilon() * scale {
+            break;
+        }
+        for p in 0..N {
+            for q in p + 1..N {
+                if a[p][q] == T::zero() {
+                    continue;
+                }
+                let theta = (a[q][q] - a[p][p]) / (a[p][q] + a[p][q]);
+                let t = theta.signum() / (theta.abs() + (theta * theta + T::one()).sqrt());
+                let c = T::one() / (t * t + T::one()).sqrt();
+                let s = t * c;
+                for row in a.iter_mut() {
+                    let (akp, akq) = (row[p], row[q]);
+                    row[p] = c * akp - s * akq;
+                    row[q] = s * akp + c * akq;
+                }
+                let (row_p, row_q) = (a[p], a[q]);
+                for k in 0..N {
+                    a[p][k] = c * row_p[k] - s * row_q[k];
+                    a[q][k] = s * row_p[k] + c * row_q[k];
+                }
+                for row in v.iter_mut() {
+                    let (vp, vq) = (row[p], row[q]);
+                    row[p] = c * vp - s * vq;
+                    row[q] = s * vp + c * vq;
+                }
+            }
+        }
+    }
+    let mut values = [T::zero(); N];
+    for (n, value) in values.iter_mut().enumerate() {
+        *value = a[n][n];
+    }
+    (values, v)
+}
@@ -0,0 +1,294 @@
+//! Solvers for Wahba's problem: the attitude best aligning weighted pairs of vector
+//! observations.
+//!
+//! The returned attitude `q` rotates body-frame vectors into the reference frame and
+//! minimizes `sum(weight * |reference - q * body * q.conj()|^2)`.
+
+use num_traits::Float;
+use crate::vector::{adjugate3, cross, determinant3, dot, mat_add, mat_mul, mat_scale, norm, scale, symmetric_eigen, transpose};
+use crate::{cast, Quaternion, QuaternionError, UnitQuaternion};
+
+#[derive(PartialEq, Copy, Clone, Debug)]
+pub struct Observation<T = f64> {
+    pub body: [T; 3],
+    pub reference: [T; 3],
+    pub weight: T,
+}
+
+impl<T: Float> Observation<T> {
+    pub fn new(body: [T; 3], reference: [T; 3], weight: T) -> Observation<T> {
+        Self { body, reference, weight }
+    }
+}
+
+/// Matrix of `p -> alpha * p` for the pure quaternion `alpha`.
+fn left_matrix<T: Float>(alpha: [T; 3]) -> [[T; 4]; 4] {
+    let [x, y, z] = alpha;
+    let o = T::zero();
+    [[o, -x, -y, -z], [x, o, -z, y], [y, z, o, -x], [z, -y, x, o]]
+}
+
+/// Matrix of `p -> p * alpha` for the pure quaternion `alpha`.
+fn right_matrix<T: Float>(alpha: [T; 3]) -> [[T; 4]; 4] {
+    let [x, y, z] = alpha;
+    let o = T::zero();
+    [[o, -x, -y, -z], [x, o, z, -y], [y, -z, o, x], [z, y, -x, o]]
+}
+
+/// Davenport's symmetric matrix `K`, for which the gain `sum(weight * <reference,
+/// q * body * q.conj()>)` equals `q^T K q`.
+pub(crate) fn davenport_matrix<T: Float>(observations: &[Observation<T>]) -> [[T; 4]; 4] {
+    observations.iter().fold([[T::zero(); 4]; 4], |k, o| {
+        let term = mat_mul(transpose(left_matrix(o.reference)), right_matrix(o.body));
+        mat_add(k, mat_scale(term, o.weight))
+    })
+}
+
+fn from_components<T: Float>(alpha: [T; 4]) -> Result<Quaternion<T>, QuaternionError> {
+    Ok(Quaternion::new(alpha[0], alpha[1], alpha[2], alpha[3]).try_unit()?.positive_hemisphere())
+}
+
+fn check<T: Float>(observations: &[Observation<T>]) -> Result<(), QuaternionError> {
+    let mut used = observations.iter().filter(|o| o.weight > T::zero() && norm(o.body) > T::zero());
+    let first = used.next().ok_or(QuaternionError::Degenerate)?;
+    let independent = used.any(|o| norm(cross(first.body, o.body)) > T::epsilon().sqrt() * norm(first.body) * norm(o.body));
+    if independent { Ok(()) } else { Err(QuaternionError::Degenerate) }
+}
+
+/// TRIAD solution from the first two observations; weights are ignored and the first
+/// observation is matched exactly.
+pub fn triad<T: Float>(observations: &[Observation<T>]) -> Result<Quaternion<T>, QuaternionError> {
+    if observations.len() < 2 {
+        return Err(QuaternionError::Degenerate);
+    }
+    let frame = |a: [T; 3], b: [T; 3]| -> Result<[[T; 3]; 3], QuaternionError> {
+        let t1 = scale(a, T::one() / norm(a));
+        let c = cross(a, b);
+        let n = norm(c);
+        if n.is_nan() || n <= T::epsilon().sqrt() * norm(a) * norm(b) {
+            return Err(QuaternionError::Degenerate);
+        }
+        let t2 = scale(c, T::one() / n);
+        Ok(transpose([t1, t2, cross(t1, t2)]))
+    };
+    let (first, second) = (observations[0], observations[1]);
+    let reference = frame(first.reference, second.reference)?;
+    let body = frame(first.body, second.body)?;
+    Ok(Quaternion::from_rotation_matrix(mat_mul(reference, transpose(body))))
+}
+
+/// Davenport's q-method: the eigenvector of `K` with the largest eigenvalue.
+pub fn davenport<T: Float>(observations: &[Observation<T>]) -> Result<Quaternion<T>, QuaternionError> {
+    check(observations)?;
+    let (values, vectors) = symmetric_eigen(davenport_matrix(observations));
+    let best = (1..4).fold(0, |b, n| if values[n] > values[b] { n } else { b });
+    from_components([vectors[0][best], vectors[1][best], vectors[2][best], vectors[3][best]])
+}
+
+/// Largest eigenvalue of `K` by Newton iteration on its characteristic polynomial,
+/// starting from the sum of the weights.
+fn largest_eigenvalue<T: Float>(k: [[T; 4]; 4], observations: &[Observation<T>]) -> T {
+    // Faddeev-LeVerrier: det(x I - K) = x^4 + c[3] x^3 + c[2] x^2 + c[1] x + c[0].
+    let mut c = [T::zero(); 4];
+    let mut m = [[T::zero(); 4]; 4];
+    let mut previous = T::one();
+    for step in 1..=4 {
+        m = mat_mul(k, m);
+        for (n, row) in m.iter_mut().enumerate() {
+            row[n] = row[n] + previous;
+        }
+        let km = mat_mul(k, m);
+        let trace = (0..4).fold(T::zero(), |t, n| t + km[n][n]);
+        previous = -trace / cast(step as f64);
+        c[4 - step] = previous;
+    }
+    let mut lambda = observations.iter().fold(T::zero(), |w, o| w + o.weight * norm(o.body) * norm(o.reference));
+    for _ in 0..32 {
+        let p = (((lambda + c[3]) * lambda + c[2]) * lambda + c[1]) * lambda + c[0];
+        let dp = ((cast::<T>(4.0) * lambda + cast::<T>(3.0) * c[3]) * lambda + cast::<T>(2.0) * c[2]) * lambda + c[1];
+        if dp == T::zero() {
+            break;
+        }
+        let step = p / dp;
+        lambda = lambda - step;
+        if step.abs() <= T::epsilon() * lambda.abs() {
+            break;
+        }
+    }
+    lambda
+}
+
+/// Splits `K - lambda I` into its scalar entry, coupling vector and vector block.
+fn blocks<T: Float>(k: [[T; 4]; 4], lambda: T) -> (T, [T; 3], [[T; 3]; 3]) {
+    let z = [k[1][0], k[2][0], k[3][0]];
+    let mut s = [[T::zero(); 3]; 3];
+    for row in 0..3 {
+        for col in 0..3 {
+            s[row][col] = k[row + 1][col + 1];
+        }
+        s[row][row] = s[row][row] - lambda;
+    }
+    (k[0][0] - lambda, z, s)
+}
+
+/// Solves in the original reference frame or, when that is ill-conditioned, in the
+/// frame rotated by 180 degrees about the coordinate axis giving the best-conditioned
+/// result: Shuster's method of sequential rotations. `solve` returns unnormalized
+/// components scaled so that their norm measures the conditioning.
+fn sequential<T: Float>(
+    observations: &[Observation<T>],
+    solve: fn(&[Observation<T>]) -> [T; 4],
+) -> Result<Quaternion<T>, QuaternionError> {
+    check(observations)?;
+    let magnitude = |q: [T; 4]| q.iter().fold(T::zero(), |m, x| m + *x * *x).sqrt();
+    let q = solve(observations);
+    if magnitude(q) >= cast(0.25) {
+        return from_components(q);
+    }
+    let mut best = (magnitude(q), Quaternion::new(q[0], q[1], q[2], q[3]));
+    for axis in 0..3 {
+        let mut e = [T::zero(); 3];
+        e[axis] = T::one();
+        let flip = Quaternion::from_vector(e);
+        let rotation = UnitQuaternion::new_unchecked(flip);
+        let rotated: Vec<Observation<T>> = observations
+            .iter()
+            .map(|o| Observation::new(o.body, rotation.rotate_vector(o.reference), o.weight))
+            .collect();
+        let q = solve(&rotated);
+        if magnitude(q) > best.0 {
+            best = (magnitude(q), flip.conj() * Quaternion::new(q[0], q[1], q[2], q[3]));
+        }
+    }
+    let q = best.1;
+    from_components([q.real(), q.vector()[0], q.vector()[1], q.vector()[2]])
+}
+
+fn quest_components<T: Float>(observations: &[Observation<T>]) -> [T; 4] {
+    let k = davenport_matrix(observations);
+    let lambda = largest_eigenvalue(k, observations);
+    let (_, z, s) = blocks(k, lambda);
+    // (K - lambda I) q = 0 gives q ~ (det(lambda I - S), adj(lambda I - S) z),
+    // which vanishes for rotations by 180 degrees.
+    let negated = mat_scale(s, -T::one());
+    let v = mat_mul(adjugate3(negated), transpose([z]));
+    let cube = lambda * lambda * lambda;
+    [determinant3(negated) / cube, v[0][0] / cube, v[1][0] / cube, v[2][0] / cube]
+}
+
+fn esoq2_components<T: Float>(observations: &[Observation<T>]) -> [T; 4] {
+    let k = davenport_matrix(observations);
+    let lambda = largest_eigenvalue(k, observations);
+    let (k00, z, s) = blocks(k, lambda);
+    // Eliminating the scalar part leaves the rank two matrix M with M e = 0 for the
+    // rotation axis e, found as the largest cross product of its rows. The result
+    // vanishes for small rotations, where M itself vanishes.
+    let outer = mat_mul(transpose([z]), [z]);
+    let m = mat_add(mat_scale(s, k00), mat_scale(outer, -T::one()));
+    let axis = [cross(m[0], m[1]), cross(m[1], m[2]), cross(m[2], m[0])]
+        .into_iter()
+        .fold([T::zero(); 3], |best, c| if norm(c) > norm(best) { c } else { best });
+    if norm(axis) == T::zero() {
+        return [T::zero(); 4];
+    }
+    let axis = scale(axis, T::one() / norm(axis));
+    let v = scale(axis, k00 / lambda);
+    [-dot(z, axis) / lambda, v[0], v[1], v[2]]
+}
+
+/// Shuster's QUEST: Newton iteration for the largest eigenvalue of `K`, then the
+/// eigenvector in closed form.
+pub fn quest<T: Float>(observations: &[Observation<T>]) -> Result<Quaternion<T>, QuaternionError> {
+    sequential(observations, quest_components)
+}
+
+/// Mortari's ESOQ2: the rotation axis as the null vector of a reduced 3x3 matrix.
+pub fn esoq2<T: Float>(observations: &[Observation<T>]) -> Result<Quaternion<T>, QuaternionError> {
+    sequential(observations, esoq2_components)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::vector::sub;
+
+    fn observations(truth: Quaternion, noise: f64) -> Vec<Observation> {
+        let rotation = UnitQuaternion::new(truth);
+        let body = [[1.0, 0.2, -0.3], [0.0, 1.0, 0.5], [-0.4, 0.3, 1.0], [0.7, -0.7, 0.1]];
+        let perturbation = [[1.0, -2.0, 0.5], [-0.3, 0.8, 1.0], [0.2, 0.1, -1.0], [-1.0, 0.4, 0.3]];
+        body.iter()
+            .zip(perturbation)
+            .enumerate()
+            .map(|(n, (b, p))| {
+                let reference = rotation.rotate_vector(*b);
+                Observation::new(*b, sub(reference, scale(p, -noise)), 1.0 + n as f64)
+            })
+            .collect()
+    }
+
+    type Solver = fn(&[Observation]) -> Result<Quaternion, QuaternionError>;
+
+    fn solvers() -> [Solver; 4] {
+        [triad, davenport, quest, esoq2]
+    }
+
+    #[test]
+    fn test_exact() {
+        for truth in [
+            Quaternion::from_axis_angle([1.0, 2.0, 3.0], 0.9),
+            Quaternion::from_axis_angle([0.0, 0.0, 1.0], 0.0),
+            Quaternion::from_axis_angle([1.0, 0.0, 0.0], 1e-9),
+            Quaternion::from_axis_angle([1.0, -1.0, 0.5], std::f64::consts::PI),
+            Quaternion::from_axis_angle([0.0, 1.0, 0.0], std::f64::consts::PI - 1e-9),
+        ] {
+            for (n, solve) in solvers().iter().enumerate() {
+                let q = solve(&observations(truth, 0.0)).unwrap();
+                assert!(q.rotation_eq(&truth, 1e-10), "solver {}: {} != {}", n, q, truth);
+            }
+        }
+    }
+    #[test]
+    fn test_noisy_agreement() {
+        let truth = Quaternion::from_axis_angle([-0.3, 0.2, 1.0], 2.2);
+        let observations = observations(truth, 1e-3);
+        let reference = davenport(&observations).unwrap();
+        assert!(quest(&observations).unwrap().rotation_eq(&reference, 1e-10));
+        assert!(esoq2(&observations).unwrap().rotation_eq(&reference, 1e-10));
+        assert!(triad(&observations).unwrap().rotation_eq(&reference, 1e-2));
+        assert!(reference.rotation_eq(&truth, 1e-2));
+        let loss = |q: Quaternion| {
+            let r = UnitQuaternion::new(q);
+            observations.iter().map(|o| o.weight * norm(sub(o.reference, r.rotate_vector(o.body))).powi(2)).sum::<f64>()
+        };
+        assert!(loss(reference) < loss(triad(&observations).unwrap()));
+        assert!(loss(reference) <= loss(truth));
+    }
+    #[test]
+    fn test_davenport_matrix() {
+        let truth = Quaternion::from_axis_angle([1.0, 2.0, 3.0], 0.9);
+        let observations = observations(truth, 0.0);
+        let k = davenport_matrix(&observations);
+        let q = [truth.real(), truth.vector()[0], truth.vector()[1], truth.vector()[2]];
+        let gain: f64 = (0..4).map(|r| (0..4).map(|c| q[r] * k[r][c] * q[c]).sum::<f64>()).sum();
+        let expected: f64 = observations.iter().map(|o| o.weight * dot(o.body, o.body)).sum();
+        assert!((gain - expected).abs() < 1e-12);
+    }
+    #[test]
+    fn test_degenerate() {
+        let one = [Observation::new([1.0, 0.0, 0.0], [0.0, 1.0, 0.0], 1.0)];
+        let parallel = [one[0], Observation::new([2.0, 0.0, 0.0], [0.0, 2.0, 0.0], 1.0)];
+        for solve in solvers() {
+            assert_eq!(solve(&one), Err(QuaternionError::Degenerate));
+            assert_eq!(solve(&parallel), Err(QuaternionError::Degenerate));
+        }
+    }
+    #[test]
+    fn test_zero_length_first() {
+        let truth = Quaternion::from_axis_angle([1.0, 2.0, 3.0], 0.9);
+        let mut observations = observations(truth, 0.0);
+        observations.insert(0, Observation::new([0.0; 3], [0.0; 3], 1.0));
+        for solve in [davenport, quest, esoq2] {
+            assert!(solve(&observations).unwrap().rotation_eq(&truth, 1e-10));
+        }
+    }
+}